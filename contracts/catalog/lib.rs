@@ -0,0 +1,299 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(min_specialization)]
+
+/// Catalog holds the Base parts which can be shared by many RMRK collections
+#[openbrush::contract]
+pub mod catalog_contract {
+    use ink_lang::codegen::{
+        EmitEvent,
+        Env,
+    };
+    use ink_prelude::vec::Vec;
+    use ink_storage::traits::SpreadAllocate;
+    use openbrush::{
+        contracts::ownable::*,
+        traits::{
+            Storage,
+            String,
+        },
+    };
+    use rmrk::{
+        impls::rmrk::types::*,
+        traits::base::*,
+    };
+
+    /// Event emitted when a new Base is registered.
+    #[ink(event)]
+    pub struct BaseAdded {
+        #[ink(topic)]
+        base: BaseId,
+        base_metadata: String,
+    }
+
+    /// Event emitted when a part is added to the Base.
+    #[ink(event)]
+    pub struct PartAdded {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        part_details: Part,
+    }
+
+    /// Event emitted when collections are allowed to be equipped into a part.
+    #[ink(event)]
+    pub struct EquippableAddressesAdded {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        equippable_addresses: Vec<AccountId>,
+    }
+
+    /// Event emitted when a collection is removed from the equippable list of a part.
+    #[ink(event)]
+    pub struct EquippableAddressRemoved {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        #[ink(topic)]
+        equippable_address: AccountId,
+    }
+
+    /// Event emitted when the equippable list of a part is cleared.
+    #[ink(event)]
+    pub struct EquippableAddressesReset {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when any collection is allowed to be equipped into a part.
+    #[ink(event)]
+    pub struct EquippableToAllSet {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when the depth or the metadata of a part is updated.
+    #[ink(event)]
+    pub struct PartUpdated {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        part_details: Part,
+    }
+
+    /// Event emitted when a part is retired.
+    #[ink(event)]
+    pub struct PartRetired {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when the Base metadata is set.
+    #[ink(event)]
+    pub struct BaseMetadataSet {
+        #[ink(topic)]
+        base: BaseId,
+        base_metadata: String,
+    }
+
+    /// Event emitted when the Base is locked.
+    #[ink(event)]
+    pub struct BaseLocked {
+        #[ink(topic)]
+        base: BaseId,
+    }
+
+    #[ink(storage)]
+    #[derive(Default, SpreadAllocate, Storage)]
+    pub struct Catalog {
+        #[storage_field]
+        ownable: ownable::Data,
+        #[storage_field]
+        base: BaseData,
+    }
+
+    impl Ownable for Catalog {}
+
+    impl Base for Catalog {}
+
+    impl Catalog {
+        /// Instantiate new Catalog contract
+        #[ink(constructor)]
+        pub fn new(catalog_metadata: String) -> Self {
+            ink_lang::codegen::initialize_contract(|instance: &mut Catalog| {
+                instance._init_with_owner(instance.env().caller());
+                instance.create_base(catalog_metadata);
+            })
+        }
+    }
+
+    impl BaseEvents for Catalog {
+        /// Used to notify listeners that a new Base is registered.
+        fn _emit_base_added_event(&self, base_id: &BaseId, base_metadata: &String) {
+            self.env().emit_event(BaseAdded {
+                base: *base_id,
+                base_metadata: base_metadata.clone(),
+            });
+        }
+
+        /// Used to notify listeners that a part is added to the Base.
+        fn _emit_part_added_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part) {
+            self.env().emit_event(PartAdded {
+                base: *base_id,
+                part: *part_id,
+                part_details: part.clone(),
+            });
+        }
+
+        /// Used to notify listeners that collection addresses are allowed to be equipped into the part.
+        fn _emit_equippable_addresses_added_event(
+            &self,
+            base_id: &BaseId,
+            part_id: &PartId,
+            equippable_addresses: &[AccountId],
+        ) {
+            self.env().emit_event(EquippableAddressesAdded {
+                base: *base_id,
+                part: *part_id,
+                equippable_addresses: equippable_addresses.to_vec(),
+            });
+        }
+
+        /// Used to notify listeners that a collection address is removed from the equippable list.
+        fn _emit_equippable_address_removed_event(
+            &self,
+            base_id: &BaseId,
+            part_id: &PartId,
+            equippable_address: &AccountId,
+        ) {
+            self.env().emit_event(EquippableAddressRemoved {
+                base: *base_id,
+                part: *part_id,
+                equippable_address: *equippable_address,
+            });
+        }
+
+        /// Used to notify listeners that the equippable list of the part is cleared.
+        fn _emit_equippable_addresses_reset_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(EquippableAddressesReset {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that any collection can be equipped into the part.
+        fn _emit_equippable_to_all_set_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(EquippableToAllSet {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that the depth or the metadataURI of the part changed.
+        fn _emit_part_updated_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part) {
+            self.env().emit_event(PartUpdated {
+                base: *base_id,
+                part: *part_id,
+                part_details: part.clone(),
+            });
+        }
+
+        /// Used to notify listeners that the part is retired.
+        fn _emit_part_retired_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(PartRetired {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that the Base metadataURI is set.
+        fn _emit_base_metadata_set_event(&self, base_id: &BaseId, base_metadata: &String) {
+            self.env().emit_event(BaseMetadataSet {
+                base: *base_id,
+                base_metadata: base_metadata.clone(),
+            });
+        }
+
+        /// Used to notify listeners that the Base is locked.
+        fn _emit_base_locked_event(&self, base_id: &BaseId) {
+            self.env().emit_event(BaseLocked { base: *base_id });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_env::test;
+        use ink_lang as ink;
+        use openbrush::contracts::psp34::PSP34Error;
+
+        const CATALOG_METADATA: &str = "ipfs://catalog_metadata";
+        const BASE_ID: BaseId = 0;
+
+        #[ink::test]
+        fn init_works() {
+            let catalog = init();
+            assert_eq!(catalog.get_bases(), vec![BASE_ID]);
+            assert_eq!(catalog.get_base_metadata(BASE_ID), CATALOG_METADATA);
+            assert_eq!(catalog.get_parts_count(BASE_ID), 0);
+            assert_eq!(1, test::recorded_events().count());
+            assert_eq!(catalog.owner(), default_accounts().alice);
+        }
+
+        #[ink::test]
+        fn add_parts_works() {
+            const EQUIPABLE_ADDRESS: [u8; 32] = [1; 32];
+            let accounts = default_accounts();
+            let mut catalog = init();
+            let part_list = vec![Part {
+                part_type: PartType::Slot,
+                z: 0,
+                equippable: vec![EQUIPABLE_ADDRESS.into()],
+                metadata_uri: String::from("ipfs://backgrounds/1.svg"),
+                is_equippable_by_all: false,
+            }];
+
+            assert!(catalog.add_part_list(BASE_ID, part_list.clone()).is_ok());
+            assert_eq!(catalog.get_parts_count(BASE_ID), 1);
+            assert_eq!(2, test::recorded_events().count());
+            assert_eq!(
+                catalog.get_part(BASE_ID, 0).unwrap().metadata_uri,
+                part_list[0].metadata_uri
+            );
+            assert!(catalog.is_equippable(BASE_ID, 0, EQUIPABLE_ADDRESS.into()));
+
+            // only owner can manage parts
+            set_sender(accounts.bob);
+            assert_eq!(
+                catalog.add_part_list(BASE_ID, part_list),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            assert_eq!(
+                catalog.set_equippable_by_all(BASE_ID, 0),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+        }
+
+        fn init() -> Catalog {
+            Catalog::new(String::from(CATALOG_METADATA))
+        }
+
+        fn default_accounts() -> test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            test::default_accounts::<Environment>()
+        }
+
+        fn set_sender(sender: AccountId) {
+            ink_env::test::set_caller::<Environment>(sender);
+        }
+    }
+}
@@ -31,6 +31,7 @@ pub mod rmrk_contract {
         },
         traits::{
             base::*,
+            equippable::*,
             minting::*,
             multiasset::*,
             nesting::*,
@@ -106,6 +107,14 @@ pub mod rmrk_contract {
         child_token_id: Id,
     }
 
+    /// Event emitted when the accepted children of a parent are reordered.
+    #[ink(event)]
+    pub struct ChildrenOrderSet {
+        #[ink(topic)]
+        parent: Id,
+        children: Vec<ChildNft>,
+    }
+
     /// Event emitted when new asset is set for the collection.
     #[ink(event)]
     pub struct AssetSet {
@@ -159,6 +168,133 @@ pub mod rmrk_contract {
         priorities: Vec<AssetId>,
     }
 
+    /// Event emitted when a child's asset is equipped into a parent's slot.
+    #[ink(event)]
+    pub struct ChildAssetEquipped {
+        #[ink(topic)]
+        token: Id,
+        #[ink(topic)]
+        asset: AssetId,
+        #[ink(topic)]
+        slot: SlotId,
+        child: ChildNft,
+        child_asset: AssetId,
+    }
+
+    /// Event emitted when a child's asset is unequipped from a parent's slot.
+    #[ink(event)]
+    pub struct ChildAssetUnequipped {
+        #[ink(topic)]
+        token: Id,
+        #[ink(topic)]
+        asset: AssetId,
+        #[ink(topic)]
+        slot: SlotId,
+    }
+
+    /// Event emitted when an equippable group is allowed to be equipped into a parent's slot.
+    #[ink(event)]
+    pub struct ValidParentEquippableGroupIdSet {
+        #[ink(topic)]
+        group: EquippableGroupId,
+        #[ink(topic)]
+        parent: AccountId,
+        base: BaseId,
+        #[ink(topic)]
+        slot: SlotId,
+    }
+
+    /// Event emitted when a new Base is registered.
+    #[ink(event)]
+    pub struct BaseAdded {
+        #[ink(topic)]
+        base: BaseId,
+        base_metadata: String,
+    }
+
+    /// Event emitted when a part is added to the Base.
+    #[ink(event)]
+    pub struct PartAdded {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        part_details: Part,
+    }
+
+    /// Event emitted when collections are allowed to be equipped into a part.
+    #[ink(event)]
+    pub struct EquippableAddressesAdded {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        equippable_addresses: Vec<AccountId>,
+    }
+
+    /// Event emitted when a collection is removed from the equippable list of a part.
+    #[ink(event)]
+    pub struct EquippableAddressRemoved {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        #[ink(topic)]
+        equippable_address: AccountId,
+    }
+
+    /// Event emitted when the equippable list of a part is cleared.
+    #[ink(event)]
+    pub struct EquippableAddressesReset {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when any collection is allowed to be equipped into a part.
+    #[ink(event)]
+    pub struct EquippableToAllSet {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when the depth or the metadata of a part is updated.
+    #[ink(event)]
+    pub struct PartUpdated {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+        part_details: Part,
+    }
+
+    /// Event emitted when a part is retired.
+    #[ink(event)]
+    pub struct PartRetired {
+        #[ink(topic)]
+        base: BaseId,
+        #[ink(topic)]
+        part: PartId,
+    }
+
+    /// Event emitted when the Base metadata is set.
+    #[ink(event)]
+    pub struct BaseMetadataSet {
+        #[ink(topic)]
+        base: BaseId,
+        base_metadata: String,
+    }
+
+    /// Event emitted when the Base is locked.
+    #[ink(event)]
+    pub struct BaseLocked {
+        #[ink(topic)]
+        base: BaseId,
+    }
+
     // Rmrk contract storage
     #[ink(storage)]
     #[derive(Default, SpreadAllocate, Storage)]
@@ -181,6 +317,8 @@ pub mod rmrk_contract {
         minting: types::MintingData,
         #[storage_field]
         base: types::BaseData,
+        #[storage_field]
+        equippable: types::EquippableData,
     }
 
     impl PSP34 for Rmrk {}
@@ -201,6 +339,8 @@ pub mod rmrk_contract {
 
     impl Base for Rmrk {}
 
+    impl Equippable for Rmrk {}
+
     impl Rmrk {
         /// Instantiate new RMRK contract
         #[ink(constructor)]
@@ -253,6 +393,37 @@ pub mod rmrk_contract {
                 approved,
             });
         }
+
+        /// Assets of the burned token are removed together with the token.
+        /// Pending children and assets don't carry over to the new owner, pending children
+        /// are returned to their senders
+        fn _before_token_transfer(
+            &mut self,
+            from: Option<&AccountId>,
+            to: Option<&AccountId>,
+            id: &Id,
+        ) -> Result<(), PSP34Error> {
+            match (from, to) {
+                (_, None) => multiasset::Internal::remove_token_assets(self, id),
+                (Some(_), Some(_)) => {
+                    nesting::Internal::return_pending_children(self, id, u32::MAX)?;
+                    multiasset::Internal::remove_pending_assets(self, id);
+                }
+                (None, Some(_)) => {}
+            }
+            Ok(())
+        }
+    }
+
+    impl nesting::Internal for Rmrk {
+        /// Equipped children can't leave the parent token
+        fn _before_child_removed(
+            &self,
+            _parent_token_id: &Id,
+            child_nft: &ChildNft,
+        ) -> Result<(), PSP34Error> {
+            equippable::Internal::ensure_not_equipped(self, child_nft)
+        }
     }
 
     impl nesting::NestingEvents for Rmrk {
@@ -301,6 +472,14 @@ pub mod rmrk_contract {
                 child_token_id: child_token_id.clone(),
             });
         }
+
+        /// Emit ChildrenOrderSet event
+        fn _emit_children_order_set_event(&self, parent: &Id, children: Vec<ChildNft>) {
+            self.env().emit_event(ChildrenOrderSet {
+                parent: parent.clone(),
+                children,
+            });
+        }
     }
     impl multiasset::MultiAssetEvents for Rmrk {
         /// Used to notify listeners that an asset object is initialized at `assetId`.
@@ -354,6 +533,150 @@ pub mod rmrk_contract {
             });
         }
     }
+
+    impl equippable::EquippableEvents for Rmrk {
+        /// Used to notify listeners that a child's asset has been equipped into one of its parent assets.
+        fn _emit_child_asset_equipped_event(
+            &self,
+            token_id: &Id,
+            asset_id: &AssetId,
+            slot_part_id: &SlotId,
+            child_nft: &ChildNft,
+            child_asset_id: &AssetId,
+        ) {
+            self.env().emit_event(ChildAssetEquipped {
+                token: token_id.clone(),
+                asset: *asset_id,
+                slot: *slot_part_id,
+                child: child_nft.clone(),
+                child_asset: *child_asset_id,
+            });
+        }
+
+        /// Used to notify listeners that a child's asset has been unequipped from one of its parent assets.
+        fn _emit_child_asset_unequipped_event(
+            &self,
+            token_id: &Id,
+            asset_id: &AssetId,
+            slot_part_id: &SlotId,
+        ) {
+            self.env().emit_event(ChildAssetUnequipped {
+                token: token_id.clone(),
+                asset: *asset_id,
+                slot: *slot_part_id,
+            });
+        }
+
+        /// Used to notify listeners that the assets belonging to a `equippable_group_id` have been marked as equippable into a given slot and parent
+        fn _emit_valid_parent_equippable_group_id_set_event(
+            &self,
+            equippable_group_id: &EquippableGroupId,
+            parent_address: &AccountId,
+            base_id: &BaseId,
+            slot_part_id: &SlotId,
+        ) {
+            self.env().emit_event(ValidParentEquippableGroupIdSet {
+                group: *equippable_group_id,
+                parent: *parent_address,
+                base: *base_id,
+                slot: *slot_part_id,
+            });
+        }
+    }
+
+    impl base::BaseEvents for Rmrk {
+        /// Used to notify listeners that a new Base is registered.
+        fn _emit_base_added_event(&self, base_id: &BaseId, base_metadata: &String) {
+            self.env().emit_event(BaseAdded {
+                base: *base_id,
+                base_metadata: base_metadata.clone(),
+            });
+        }
+
+        /// Used to notify listeners that a part is added to the Base.
+        fn _emit_part_added_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part) {
+            self.env().emit_event(PartAdded {
+                base: *base_id,
+                part: *part_id,
+                part_details: part.clone(),
+            });
+        }
+
+        /// Used to notify listeners that collection addresses are allowed to be equipped into the part.
+        fn _emit_equippable_addresses_added_event(
+            &self,
+            base_id: &BaseId,
+            part_id: &PartId,
+            equippable_addresses: &[AccountId],
+        ) {
+            self.env().emit_event(EquippableAddressesAdded {
+                base: *base_id,
+                part: *part_id,
+                equippable_addresses: equippable_addresses.to_vec(),
+            });
+        }
+
+        /// Used to notify listeners that a collection address is removed from the equippable list.
+        fn _emit_equippable_address_removed_event(
+            &self,
+            base_id: &BaseId,
+            part_id: &PartId,
+            equippable_address: &AccountId,
+        ) {
+            self.env().emit_event(EquippableAddressRemoved {
+                base: *base_id,
+                part: *part_id,
+                equippable_address: *equippable_address,
+            });
+        }
+
+        /// Used to notify listeners that the equippable list of the part is cleared.
+        fn _emit_equippable_addresses_reset_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(EquippableAddressesReset {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that any collection can be equipped into the part.
+        fn _emit_equippable_to_all_set_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(EquippableToAllSet {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that the depth or the metadataURI of the part changed.
+        fn _emit_part_updated_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part) {
+            self.env().emit_event(PartUpdated {
+                base: *base_id,
+                part: *part_id,
+                part_details: part.clone(),
+            });
+        }
+
+        /// Used to notify listeners that the part is retired.
+        fn _emit_part_retired_event(&self, base_id: &BaseId, part_id: &PartId) {
+            self.env().emit_event(PartRetired {
+                base: *base_id,
+                part: *part_id,
+            });
+        }
+
+        /// Used to notify listeners that the Base metadataURI is set.
+        fn _emit_base_metadata_set_event(&self, base_id: &BaseId, base_metadata: &String) {
+            self.env().emit_event(BaseMetadataSet {
+                base: *base_id,
+                base_metadata: base_metadata.clone(),
+            });
+        }
+
+        /// Used to notify listeners that the Base is locked.
+        fn _emit_base_locked_event(&self, base_id: &BaseId) {
+            self.env().emit_event(BaseLocked { base: *base_id });
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -366,7 +689,10 @@ pub mod rmrk_contract {
         use ink_lang as ink;
         use ink_prelude::string::String as PreludeString;
         use rmrk::impls::rmrk::{
-            errors::RmrkError,
+            errors::{
+                child_contract_error,
+                RmrkError,
+            },
             minting::Internal,
         };
 
@@ -374,6 +700,36 @@ pub mod rmrk_contract {
         const BASE_URI: &str = "ipfs://myIpfsUri/";
         const MAX_SUPPLY: u64 = 10;
 
+        /// Parent collection whose children live in a collection which traps on every transfer.
+        /// The child collection is PSP34 only, so recording the parent succeeds as a no-op.
+        #[derive(Default, Storage)]
+        struct FailingChildParent {
+            #[storage_field]
+            psp34: psp34::Data<enumerable::Balances>,
+            #[storage_field]
+            ownable: ownable::Data,
+            #[storage_field]
+            nesting: types::NestingData,
+        }
+
+        impl nesting::Internal for FailingChildParent {
+            fn transfer_child_ownership(
+                &self,
+                _to: AccountId,
+                _child_nft: ChildNft,
+            ) -> Result<(), PSP34Error> {
+                Err(child_contract_error(ink_env::Error::CalleeTrapped))
+            }
+
+            fn set_child_parent(
+                &self,
+                _child_nft: &ChildNft,
+                _parent_token_id: Option<Id>,
+            ) -> Result<(), PSP34Error> {
+                Ok(())
+            }
+        }
+
         #[ink::test]
         fn init_works() {
             let rmrk = init();
@@ -496,116 +852,942 @@ pub mod rmrk_contract {
         }
 
         #[ink::test]
-        fn withdrawal_works() {
+        fn nest_mint_works() {
+            const PARENT_ID: Id = Id::U64(1);
             let mut rmrk = init();
             let accounts = default_accounts();
-            set_balance(accounts.bob, PRICE);
-            set_sender(accounts.bob);
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
 
-            assert!(pay_with_call!(rmrk.mint_next(), PRICE).is_ok());
-            let expected_contract_balance = PRICE + rmrk.env().minimum_balance();
-            assert_eq!(rmrk.env().balance(), expected_contract_balance);
+            // parent owner mints children straight into the parent
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 2);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 2).is_ok());
+            assert_eq!(rmrk.total_supply(), 3);
+            assert_eq!(rmrk.owner_of(Id::U64(2)), Some(this));
+            assert_eq!(rmrk.owner_of(Id::U64(3)), Some(this));
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 0)));
+            // 3 transfer events, 2 ChildAdded and 2 ChildAccepted events
+            assert_eq!(7, ink_env::test::recorded_events().count());
 
-            // Bob fails to withdraw
+            // children minted by someone else are pending
             set_sender(accounts.bob);
-            assert!(rmrk.withdraw().is_err());
-            assert_eq!(rmrk.env().balance(), expected_contract_balance);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 1)));
 
-            // Alice (contract owner) withdraws. Existential minimum is still set
-            set_sender(accounts.alice);
-            assert!(rmrk.withdraw().is_ok());
-            // assert_eq!(rmrk.env().balance(), rmrk.env().minimum_balance());
+            // mint value, amount and parent token are checked
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE - 1);
+            assert_eq!(
+                rmrk.nest_mint(this, PARENT_ID, 1),
+                Err(PSP34Error::Custom(RmrkError::BadMintValue.as_str()))
+            );
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(0);
+            assert_eq!(
+                rmrk.nest_mint(this, PARENT_ID, 0),
+                Err(PSP34Error::Custom(RmrkError::CannotMintZeroTokens.as_str()))
+            );
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert_eq!(rmrk.nest_mint(this, Id::U64(42), 1), Err(TokenNotExists));
+            assert_eq!(rmrk.total_supply(), 4);
         }
 
         #[ink::test]
-        fn token_uri_works() {
+        fn burn_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const GRANDCHILD_ID: Id = Id::U64(3);
+            const ASSET_ID: AssetId = 1;
             let mut rmrk = init();
             let accounts = default_accounts();
+            let this = rmrk.env().account_id();
             set_sender(accounts.alice);
-
             test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
-            assert!(rmrk.mint_next().is_ok());
-            // return error if request is for not yet minted token
-            assert_eq!(rmrk.token_uri(42), Err(TokenNotExists));
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert!(rmrk.mint(this, 1).is_ok());
+            rmrk.nesting
+                .accepted_children
+                .insert(CHILD_ID, &vec![(this, GRANDCHILD_ID)]);
+            assert!(rmrk
+                .add_asset_entry(ASSET_ID, 0, None, None, String::from("asset_uri/"), vec![])
+                .is_ok());
+            assert!(rmrk.add_asset_to_token(PARENT_ID, ASSET_ID, None).is_ok());
+
+            // only token owner can burn
+            set_sender(accounts.bob);
             assert_eq!(
-                rmrk.token_uri(1),
-                Ok(PreludeString::from(BASE_URI.to_owned() + "1.json"))
+                rmrk.burn(PARENT_ID, 2),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
             );
 
-            // return error if request is for not yet minted token
-            assert_eq!(rmrk.token_uri(42), Err(TokenNotExists));
-
-            // verify token_uri when baseUri is empty
+            // all the children must fit into max_recursive_burns
             set_sender(accounts.alice);
-            assert!(rmrk.set_base_uri(PreludeString::from("")).is_ok());
             assert_eq!(
-                rmrk.token_uri(1),
-                Ok("".to_owned() + &PreludeString::from("1.json"))
+                rmrk.burn(PARENT_ID, 1),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxRecursiveBurnsReached.as_str()
+                ))
             );
-        }
+            assert_eq!(rmrk.total_supply(), 3);
 
-        #[ink::test]
-        fn owner_is_set() {
-            let accounts = default_accounts();
-            let rmrk = init();
-            assert_eq!(rmrk.owner(), accounts.alice);
+            assert_eq!(rmrk.burn(PARENT_ID, 2), Ok(2));
+            assert_eq!(rmrk.total_supply(), 0);
+            assert_eq!(rmrk.owner_of(GRANDCHILD_ID), None);
+            assert_eq!(rmrk.children_balance(PARENT_ID), Err(TokenNotExists));
+            assert_eq!(rmrk.nesting.accepted_children.get(CHILD_ID), None);
+            assert_eq!(rmrk.nesting.parents.get(CHILD_ID), None);
+            assert_eq!(rmrk.multiasset.accepted_assets.get(PARENT_ID), None);
+            assert_eq!(rmrk.burn(PARENT_ID, 2), Err(TokenNotExists));
         }
 
         #[ink::test]
-        fn set_base_uri_works() {
-            let accounts = default_accounts();
-            const NEW_BASE_URI: &str = "new_uri/";
+        fn burn_returns_pending_children() {
+            const PARENT_ID: Id = Id::U64(1);
+            const PENDING_CHILD_ID: Id = Id::U64(2);
             let mut rmrk = init();
-
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
             set_sender(accounts.alice);
-            let collection_id = rmrk.collection_id();
-            assert!(rmrk.set_base_uri(NEW_BASE_URI.into()).is_ok());
-            assert_eq!(
-                rmrk.get_attribute(collection_id, String::from("baseUri")),
-                Some(String::from(NEW_BASE_URI))
-            );
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
             set_sender(accounts.bob);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+
+            // pending child goes back to its sender, not to the parent owner
+            set_sender(accounts.alice);
+            assert_eq!(rmrk.burn(PARENT_ID, 0), Ok(0));
+            assert_eq!(rmrk.owner_of(PENDING_CHILD_ID), Some(accounts.bob));
+            assert_eq!(rmrk.parent_of(PENDING_CHILD_ID), None);
             assert_eq!(
-                rmrk.set_base_uri(NEW_BASE_URI.into()),
-                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+                rmrk.nesting.child_senders.get((this, PENDING_CHILD_ID)),
+                None
             );
         }
 
         #[ink::test]
-        fn check_supply_overflow_ok() {
+        fn burn_nested_token_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const PENDING_CHILD_ID: Id = Id::U64(3);
+            let mut rmrk = init();
             let accounts = default_accounts();
-            let max_supply = u64::MAX - 1;
-            let mut rmrk = Rmrk::new(
-                String::from("Remark Project"),
-                String::from("RMK"),
-                String::from(BASE_URI),
-                max_supply,
-                PRICE,
-                String::from(BASE_URI),
-                accounts.eve,
-                0,
-            );
-            rmrk.minting.last_token_id = max_supply - 1;
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
 
-            // check case when last_token_id.add(mint_amount) if more than u64::MAX
+            // pending child is rejected, not burned
+            set_sender(accounts.alice);
             assert_eq!(
-                rmrk._check_amount(3),
-                Err(PSP34Error::Custom(RmrkError::CollectionIsFull.as_str()))
+                rmrk.burn(PENDING_CHILD_ID, 0),
+                Err(PSP34Error::Custom(RmrkError::ChildNotFound.as_str()))
             );
 
-            // check case when mint_amount is 0
-            assert_eq!(
-                rmrk._check_amount(0),
-                Err(PSP34Error::Custom(RmrkError::CannotMintZeroTokens.as_str()))
-            );
+            // root owner burns the nested token, the parent forgets it
+            assert_eq!(rmrk.burn(CHILD_ID, 0), Ok(0));
+            assert_eq!(rmrk.owner_of(CHILD_ID), None);
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((0, 1)));
+            assert_eq!(rmrk.child_index(PARENT_ID, (this, CHILD_ID)), Ok(None));
+
+            // parent can still be burned
+            assert_eq!(rmrk.burn(PARENT_ID, 0), Ok(0));
+            assert_eq!(rmrk.owner_of(PENDING_CHILD_ID), Some(accounts.bob));
+            assert_eq!(rmrk.total_supply(), 1);
         }
 
         #[ink::test]
-        fn check_value_overflow_ok() {
+        fn root_owner_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const GRANDCHILD_ID: Id = Id::U64(3);
+            const ASSET_ID: AssetId = 1;
+            let mut rmrk = init();
             let accounts = default_accounts();
-            let max_supply = u64::MAX;
-            let price = u128::MAX as u128;
-            let rmrk = Rmrk::new(
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert!(rmrk.nest_mint(this, CHILD_ID, 1).is_ok());
+
+            assert_eq!(rmrk.direct_owner_of(PARENT_ID), Ok((accounts.alice, None)));
+            assert_eq!(rmrk.direct_owner_of(CHILD_ID), Ok((this, Some(PARENT_ID))));
+            assert_eq!(
+                rmrk.direct_owner_of(GRANDCHILD_ID),
+                Ok((this, Some(CHILD_ID)))
+            );
+            assert_eq!(rmrk.root_owner_of(GRANDCHILD_ID), Ok(accounts.alice));
+            assert_eq!(rmrk.root_owner_of(Id::U64(42)), Err(TokenNotExists));
+
+            // root owner manages children and assets of the nested tokens
+            assert_eq!(rmrk.children_balance(CHILD_ID), Ok((0, 1)));
+            assert!(rmrk.accept_child(CHILD_ID, (this, GRANDCHILD_ID)).is_ok());
+            assert_eq!(rmrk.children_balance(CHILD_ID), Ok((1, 0)));
+            assert!(rmrk
+                .add_asset_entry(ASSET_ID, 0, None, None, String::from("asset_uri/"), vec![])
+                .is_ok());
+            assert!(rmrk.add_asset_to_token(CHILD_ID, ASSET_ID, None).is_ok());
+            assert_eq!(
+                rmrk.get_accepted_token_assets(CHILD_ID),
+                Ok(Some(vec![ASSET_ID]))
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.set_priority(CHILD_ID, vec![ASSET_ID]),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+
+            // only the owner collection can set the parent
+            assert_eq!(
+                rmrk.set_parent(PARENT_ID, Some(CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+        }
+
+        #[ink::test]
+        fn parent_of_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const NEW_PARENT_ID: Id = Id::U64(3);
+            const PENDING_CHILD_ID: Id = Id::U64(4);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert_eq!(rmrk.parent_of(PARENT_ID), None);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert_eq!(rmrk.parent_of(CHILD_ID), Some((this, PARENT_ID)));
+
+            // the parent follows the child between parents
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk
+                .transfer_child(PARENT_ID, NEW_PARENT_ID, (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.parent_of(CHILD_ID), Some((this, NEW_PARENT_ID)));
+
+            // pending child has a parent until it is rejected and returned to its sender
+            set_sender(accounts.bob);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert_eq!(rmrk.parent_of(PENDING_CHILD_ID), Some((this, PARENT_ID)));
+            set_sender(accounts.alice);
+            assert!(rmrk
+                .reject_child(PARENT_ID, (this, PENDING_CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.parent_of(PENDING_CHILD_ID), None);
+            assert_eq!(rmrk.owner_of(PENDING_CHILD_ID), Some(accounts.bob));
+            assert_eq!(rmrk.balance_of(accounts.bob), 1);
+            assert_eq!(rmrk.parent_of(Id::U64(42)), None);
+        }
+
+        #[ink::test]
+        fn allowed_child_collections_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            let child_collection = accounts.charlie;
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+
+            // own collection is always allowed, others need approval
+            assert!(rmrk.is_allowed(this));
+            assert!(!rmrk.is_allowed(child_collection));
+            assert_eq!(
+                rmrk.add_child(PARENT_ID, (child_collection, Id::U64(1))),
+                Err(PSP34Error::Custom(
+                    RmrkError::ChildContractNotApproved.as_str()
+                ))
+            );
+
+            // only contract owner can manage approved collections
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.add_allowed_child_collection(child_collection),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            assert_eq!(
+                rmrk.set_allow_all_child_collections(true),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+
+            set_sender(accounts.alice);
+            assert!(rmrk.add_allowed_child_collection(child_collection).is_ok());
+            assert!(rmrk.is_allowed(child_collection));
+            assert!(rmrk
+                .remove_allowed_child_collection(child_collection)
+                .is_ok());
+            assert!(!rmrk.is_allowed(child_collection));
+            assert!(rmrk.set_allow_all_child_collections(true).is_ok());
+            assert!(rmrk.is_allowed(child_collection));
+        }
+
+        #[ink::test]
+        fn nesting_cycle_and_depth_checks_work() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const GRANDCHILD_ID: Id = Id::U64(3);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert!(rmrk.nest_mint(this, CHILD_ID, 1).is_ok());
+            assert!(rmrk.accept_child(CHILD_ID, (this, GRANDCHILD_ID)).is_ok());
+
+            // token can't be nested into itself or into its descendants
+            assert_eq!(
+                rmrk.transfer_child(PARENT_ID, CHILD_ID, (this, CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NestingCycleDetected.as_str()))
+            );
+            assert_eq!(
+                rmrk.transfer_child(PARENT_ID, GRANDCHILD_ID, (this, CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NestingCycleDetected.as_str()))
+            );
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((1, 0)));
+
+            // only contract owner can set the maximum depth
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.set_max_nesting_depth(2),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            set_sender(accounts.alice);
+            assert_eq!(rmrk.max_nesting_depth(), 0);
+            assert!(rmrk.set_max_nesting_depth(2).is_ok());
+            assert_eq!(rmrk.max_nesting_depth(), 2);
+            assert_eq!(
+                rmrk.nest_mint(this, GRANDCHILD_ID, 1),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxNestingDepthExceeded.as_str()
+                ))
+            );
+            assert!(rmrk.nest_mint(this, CHILD_ID, 1).is_ok());
+
+            // children of a nested token count against the maximum depth
+            assert_eq!(rmrk.children_depth(PARENT_ID), Ok(2));
+            assert_eq!(rmrk.children_depth(CHILD_ID), Ok(1));
+            assert_eq!(rmrk.children_depth(GRANDCHILD_ID), Ok(0));
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, Id::U64(5), 1).is_ok());
+            assert_eq!(
+                rmrk.transfer_child(PARENT_ID, Id::U64(6), (this, CHILD_ID)),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxNestingDepthExceeded.as_str()
+                ))
+            );
+            assert!(rmrk
+                .transfer_child(PARENT_ID, Id::U64(5), (this, CHILD_ID))
+                .is_ok());
+
+            // child moved under a nested token of the same root owner is accepted
+            assert!(rmrk.set_max_nesting_depth(0).is_ok());
+            assert!(rmrk
+                .transfer_child(Id::U64(5), Id::U64(6), (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.children_balance(Id::U64(6)), Ok((1, 0)));
+        }
+
+        #[ink::test]
+        fn children_queries_work() {
+            const PARENT_ID: Id = Id::U64(1);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 3);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 3).is_ok());
+            set_sender(accounts.bob);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+
+            assert_eq!(
+                rmrk.get_accepted_children(PARENT_ID, 0, 10),
+                Ok(vec![
+                    (this, Id::U64(2)),
+                    (this, Id::U64(3)),
+                    (this, Id::U64(4))
+                ])
+            );
+            assert_eq!(
+                rmrk.get_accepted_children(PARENT_ID, 1, 1),
+                Ok(vec![(this, Id::U64(3))])
+            );
+            assert_eq!(rmrk.get_accepted_children(PARENT_ID, 3, 10), Ok(vec![]));
+            assert_eq!(
+                rmrk.get_pending_children(PARENT_ID, 0, 10),
+                Ok(vec![(this, Id::U64(5))])
+            );
+            assert_eq!(rmrk.child_index(PARENT_ID, (this, Id::U64(4))), Ok(Some(2)));
+            assert_eq!(rmrk.child_index(PARENT_ID, (this, Id::U64(5))), Ok(None));
+            assert_eq!(
+                rmrk.get_accepted_children(Id::U64(42), 0, 10),
+                Err(TokenNotExists)
+            );
+        }
+
+        #[ink::test]
+        fn approved_operator_manages_children() {
+            const PARENT_ID: Id = Id::U64(1);
+            const NEW_PARENT_ID: Id = Id::U64(2);
+            const CHILD_ID: Id = Id::U64(3);
+            const PENDING_CHILD_ID: Id = Id::U64(4);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 2);
+            assert!(rmrk.mint(accounts.alice, 2).is_ok());
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            set_sender(accounts.charlie);
+            assert!(rmrk.nest_mint(this, NEW_PARENT_ID, 1).is_ok());
+
+            // only parent owner or approved operator can move the child
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.transfer_child(PARENT_ID, NEW_PARENT_ID, (this, CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+            set_sender(accounts.alice);
+            assert!(rmrk.approve(accounts.bob, Some(PARENT_ID), true).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk
+                .transfer_child(PARENT_ID, NEW_PARENT_ID, (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.children_balance(NEW_PARENT_ID), Ok((1, 1)));
+
+            // approval of a single token doesn't cover the other parent
+            assert_eq!(
+                rmrk.reject_child(NEW_PARENT_ID, (this, PENDING_CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+            set_sender(accounts.alice);
+            assert!(rmrk.approve(accounts.bob, None, true).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk
+                .reject_child(NEW_PARENT_ID, (this, PENDING_CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.children_balance(NEW_PARENT_ID), Ok((1, 0)));
+        }
+
+        #[ink::test]
+        fn remove_child_from_nested_parent_works() {
+            const ROOT_ID: Id = Id::U64(1);
+            const NESTED_PARENT_ID: Id = Id::U64(2);
+            const CHILD_ID: Id = Id::U64(3);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, ROOT_ID, 1).is_ok());
+            assert!(rmrk.nest_mint(this, NESTED_PARENT_ID, 1).is_ok());
+            assert_eq!(rmrk.owner_of(NESTED_PARENT_ID), Some(this));
+
+            // removed child goes to the root owner, not to the collection holding the parent
+            assert!(rmrk
+                .remove_child(NESTED_PARENT_ID, (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.owner_of(CHILD_ID), Some(accounts.alice));
+            assert_eq!(rmrk.parent_of(CHILD_ID), None);
+            assert_eq!(rmrk.children_balance(NESTED_PARENT_ID), Ok((0, 0)));
+            assert_eq!(rmrk.children_balance(ROOT_ID), Ok((1, 0)));
+        }
+
+        #[ink::test]
+        fn nested_parent_operator_works() {
+            const ROOT_ID: Id = Id::U64(1);
+            const NESTED_PARENT_ID: Id = Id::U64(2);
+            const CHILD_ID: Id = Id::U64(3);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert!(rmrk.nest_mint(this, ROOT_ID, 1).is_ok());
+            assert!(rmrk.nest_mint(this, NESTED_PARENT_ID, 1).is_ok());
+
+            // nested token is held by the collection, the root owner can't approve it
+            assert_eq!(
+                rmrk.approve(accounts.bob, Some(NESTED_PARENT_ID), true),
+                Err(NotApproved)
+            );
+            // approval of the root token doesn't reach its children
+            assert!(rmrk.approve(accounts.bob, Some(ROOT_ID), true).is_ok());
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.accept_child(NESTED_PARENT_ID, (this, CHILD_ID)),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+
+            // operator approved for all tokens manages the nested parent
+            set_sender(accounts.alice);
+            assert!(rmrk.approve(accounts.bob, None, true).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk
+                .accept_child(NESTED_PARENT_ID, (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.children_balance(NESTED_PARENT_ID), Ok((1, 0)));
+
+            // removed child goes to the root owner, not to the operator
+            assert!(rmrk
+                .remove_child(NESTED_PARENT_ID, (this, CHILD_ID))
+                .is_ok());
+            assert_eq!(rmrk.owner_of(CHILD_ID), Some(accounts.alice));
+            assert_eq!(rmrk.children_balance(NESTED_PARENT_ID), Ok((0, 0)));
+        }
+
+        #[ink::test]
+        fn children_limits_work() {
+            const PARENT_ID: Id = Id::U64(1);
+            const PENDING_CHILD_ID: Id = Id::U64(4);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.set_children_limits(2, 1),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            set_sender(accounts.alice);
+            assert_eq!(rmrk.children_limits(), (0, 0));
+            assert!(rmrk.set_children_limits(2, 1).is_ok());
+            assert_eq!(rmrk.children_limits(), (2, 1));
+
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 2);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 2).is_ok());
+            set_sender(accounts.bob);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 1)));
+            assert_eq!(
+                rmrk.nesting.child_senders.get((this, PENDING_CHILD_ID)),
+                Some(accounts.bob)
+            );
+
+            // each list has its own cap
+            assert_eq!(
+                rmrk.nest_mint(this, PARENT_ID, 1),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxPendingChildrenReached.as_str()
+                ))
+            );
+            set_sender(accounts.alice);
+            assert_eq!(
+                rmrk.nest_mint(this, PARENT_ID, 1),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxAcceptedChildrenReached.as_str()
+                ))
+            );
+
+            // rejected calls leave no trace
+            assert_eq!(rmrk.total_supply(), 4);
+            assert_eq!(rmrk.minting.last_token_id, 4);
+            assert_eq!(rmrk.owner_of(Id::U64(5)), None);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert_eq!(
+                rmrk.nest_transfer(this, PARENT_ID, Id::U64(5)),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxAcceptedChildrenReached.as_str()
+                ))
+            );
+            assert_eq!(rmrk.owner_of(Id::U64(5)), Some(accounts.alice));
+            assert_eq!(
+                rmrk.accept_child(PARENT_ID, (this, PENDING_CHILD_ID)),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxAcceptedChildrenReached.as_str()
+                ))
+            );
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 1)));
+            assert_eq!(
+                rmrk.nesting.child_senders.get((this, PENDING_CHILD_ID)),
+                Some(accounts.bob)
+            );
+
+            // only parent owner can reject all, within the expected number of children
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.reject_all_children(PARENT_ID, 1),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+            set_sender(accounts.alice);
+            assert_eq!(
+                rmrk.reject_all_children(PARENT_ID, 0),
+                Err(PSP34Error::Custom(RmrkError::MaxRejectionsReached.as_str()))
+            );
+            assert!(rmrk.reject_all_children(Id::U64(2), 0).is_ok());
+        }
+
+        #[ink::test]
+        fn nest_transfer_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const BOB_TOKEN_ID: Id = Id::U64(3);
+            const ALICE_TOKEN_ID: Id = Id::U64(4);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 2);
+            assert!(rmrk.mint(accounts.alice, 2).is_ok());
+            set_sender(accounts.bob);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.bob, 1).is_ok());
+            set_sender(accounts.alice);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+
+            // parent owner nests the token straight into accepted children
+            assert!(rmrk.nest_transfer(this, PARENT_ID, CHILD_ID).is_ok());
+            assert_eq!(rmrk.owner_of(CHILD_ID), Some(this));
+            assert_eq!(rmrk.parent_of(CHILD_ID), Some((this, PARENT_ID)));
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((1, 0)));
+
+            // token of another owner is pending and remembers its sender
+            set_sender(accounts.bob);
+            assert!(rmrk.nest_transfer(this, PARENT_ID, BOB_TOKEN_ID).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((1, 1)));
+            assert_eq!(
+                rmrk.nesting.child_senders.get((this, BOB_TOKEN_ID)),
+                Some(accounts.bob)
+            );
+
+            // only token owner can nest it, never into its own descendant
+            assert_eq!(
+                rmrk.nest_transfer(this, PARENT_ID, ALICE_TOKEN_ID),
+                Err(NotApproved)
+            );
+            set_sender(accounts.alice);
+            assert_eq!(
+                rmrk.nest_transfer(this, CHILD_ID, PARENT_ID),
+                Err(PSP34Error::Custom(RmrkError::NestingCycleDetected.as_str()))
+            );
+            assert_eq!(
+                rmrk.nest_transfer(this, Id::U64(42), ALICE_TOKEN_ID),
+                Err(TokenNotExists)
+            );
+        }
+
+        #[ink::test]
+        fn child_contract_errors_are_mapped() {
+            // no contract at the child address
+            assert_eq!(
+                child_contract_error(ink_env::Error::NotCallable),
+                PSP34Error::Custom(RmrkError::ChildContractNotPsp34.as_str())
+            );
+            assert_eq!(
+                child_contract_error(ink_env::Error::CodeNotFound),
+                PSP34Error::Custom(RmrkError::ChildContractNotPsp34.as_str())
+            );
+            // child contract trapped or reverted
+            assert_eq!(
+                child_contract_error(ink_env::Error::CalleeTrapped),
+                PSP34Error::Custom(RmrkError::ChildContractCallFailed.as_str())
+            );
+            assert_eq!(
+                child_contract_error(ink_env::Error::CalleeReverted),
+                PSP34Error::Custom(RmrkError::ChildContractCallFailed.as_str())
+            );
+        }
+
+        #[ink::test]
+        fn failing_child_contract_keeps_state() {
+            const PARENT_ID: Id = Id::U64(1);
+            let mut parent = FailingChildParent::default();
+            let accounts = default_accounts();
+            let accepted_child = (accounts.django, Id::U64(1));
+            let pending_child = (accounts.django, Id::U64(2));
+            set_sender(accounts.alice);
+            assert!(parent
+                .data::<psp34::Data<enumerable::Balances>>()
+                ._mint_to(accounts.alice, PARENT_ID)
+                .is_ok());
+            parent
+                .nesting
+                .accepted_children
+                .insert(&PARENT_ID, &vec![accepted_child.clone()]);
+            parent
+                .nesting
+                .pending_children
+                .insert(&PARENT_ID, &vec![pending_child.clone()]);
+            parent
+                .nesting
+                .child_senders
+                .insert(&pending_child, &accounts.bob);
+
+            // the child contract fails, the children stay with the parent
+            assert_eq!(
+                parent.remove_child(PARENT_ID, accepted_child.clone()),
+                Err(PSP34Error::Custom(
+                    RmrkError::ChildContractCallFailed.as_str()
+                ))
+            );
+            assert_eq!(
+                parent.reject_all_children(PARENT_ID, 1),
+                Err(PSP34Error::Custom(
+                    RmrkError::ChildContractCallFailed.as_str()
+                ))
+            );
+            assert_eq!(parent.children_balance(PARENT_ID), Ok((1, 1)));
+            assert_eq!(parent.child_index(PARENT_ID, accepted_child), Ok(Some(0)));
+            assert_eq!(
+                parent.nesting.child_senders.get(&pending_child),
+                Some(accounts.bob)
+            );
+        }
+
+        #[ink::test]
+        fn set_children_order_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            let child = |id: u64| (this, Id::U64(id));
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 3);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 3).is_ok());
+
+            assert!(rmrk
+                .set_children_order(PARENT_ID, vec![child(4), child(2), child(3)])
+                .is_ok());
+            assert_eq!(
+                rmrk.get_accepted_children(PARENT_ID, 0, 10),
+                Ok(vec![child(4), child(2), child(3)])
+            );
+
+            // new order must be a permutation of the accepted children
+            assert_eq!(
+                rmrk.set_children_order(PARENT_ID, vec![child(4), child(2)]),
+                Err(PSP34Error::Custom(RmrkError::BadPriorityLength.as_str()))
+            );
+            assert_eq!(
+                rmrk.set_children_order(PARENT_ID, vec![child(4), child(4), child(3)]),
+                Err(PSP34Error::Custom(RmrkError::ChildNotFound.as_str()))
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.set_children_order(PARENT_ID, vec![child(2), child(3), child(4)]),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+        }
+
+        #[ink::test]
+        fn batch_children_works() {
+            const PARENT_ID: Id = Id::U64(1);
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            let this = rmrk.env().account_id();
+            let child = |id: u64| (this, Id::U64(id));
+            set_sender(accounts.alice);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            set_sender(accounts.bob);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 3);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 3).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((0, 3)));
+
+            // only parent owner can accept or reject
+            assert_eq!(
+                rmrk.accept_children(PARENT_ID, vec![child(2), child(3)]),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+            set_sender(accounts.alice);
+            assert!(rmrk
+                .accept_children(PARENT_ID, vec![child(2), child(3)])
+                .is_ok());
+            assert!(rmrk.reject_children(PARENT_ID, vec![child(4)]).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 0)));
+            assert_eq!(rmrk.owner_of(Id::U64(4)), Some(accounts.bob));
+            assert_eq!(
+                rmrk.get_accepted_children(PARENT_ID, 0, 10),
+                Ok(vec![child(2), child(3)])
+            );
+
+            // batch size is bounded
+            let too_many = vec![child(2); MAX_BATCH_CHILDREN as usize + 1];
+            assert_eq!(
+                rmrk.add_children(PARENT_ID, too_many.clone()),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxBatchChildrenExceeded.as_str()
+                ))
+            );
+            assert_eq!(
+                rmrk.accept_children(PARENT_ID, too_many.clone()),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxBatchChildrenExceeded.as_str()
+                ))
+            );
+            assert_eq!(
+                rmrk.reject_children(PARENT_ID, too_many),
+                Err(PSP34Error::Custom(
+                    RmrkError::MaxBatchChildrenExceeded.as_str()
+                ))
+            );
+
+            // a child which is not pending fails the whole call
+            assert_eq!(
+                rmrk.reject_children(PARENT_ID, vec![child(2)]),
+                Err(PSP34Error::Custom(RmrkError::AlreadyAddedChild.as_str()))
+            );
+
+            // a failing batch leaves every child where it was
+            set_sender(accounts.bob);
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.nest_mint(this, PARENT_ID, 1).is_ok());
+            set_sender(accounts.alice);
+            assert_eq!(
+                rmrk.accept_children(PARENT_ID, vec![child(5), child(4)]),
+                Err(PSP34Error::Custom(RmrkError::ChildNotFound.as_str()))
+            );
+            assert_eq!(
+                rmrk.accept_children(PARENT_ID, vec![child(5), child(5)]),
+                Err(PSP34Error::Custom(RmrkError::AlreadyAddedChild.as_str()))
+            );
+            assert_eq!(
+                rmrk.reject_children(PARENT_ID, vec![child(5), child(4)]),
+                Err(PSP34Error::Custom(RmrkError::ChildNotFound.as_str()))
+            );
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 1)));
+            assert_eq!(rmrk.owner_of(Id::U64(5)), Some(this));
+            assert!(rmrk.mint(accounts.alice, 1).is_ok());
+            assert_eq!(
+                rmrk.add_children(PARENT_ID, vec![child(6), child(4)]),
+                Err(NotApproved)
+            );
+            assert_eq!(rmrk.owner_of(Id::U64(6)), Some(accounts.alice));
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((2, 1)));
+            assert!(rmrk.add_children(PARENT_ID, vec![child(6)]).is_ok());
+            assert_eq!(rmrk.children_balance(PARENT_ID), Ok((3, 1)));
+        }
+
+        #[ink::test]
+        fn withdrawal_works() {
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            set_balance(accounts.bob, PRICE);
+            set_sender(accounts.bob);
+
+            assert!(pay_with_call!(rmrk.mint_next(), PRICE).is_ok());
+            let expected_contract_balance = PRICE + rmrk.env().minimum_balance();
+            assert_eq!(rmrk.env().balance(), expected_contract_balance);
+
+            // Bob fails to withdraw
+            set_sender(accounts.bob);
+            assert!(rmrk.withdraw().is_err());
+            assert_eq!(rmrk.env().balance(), expected_contract_balance);
+
+            // Alice (contract owner) withdraws. Existential minimum is still set
+            set_sender(accounts.alice);
+            assert!(rmrk.withdraw().is_ok());
+            // assert_eq!(rmrk.env().balance(), rmrk.env().minimum_balance());
+        }
+
+        #[ink::test]
+        fn token_uri_works() {
+            let mut rmrk = init();
+            let accounts = default_accounts();
+            set_sender(accounts.alice);
+
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint_next().is_ok());
+            // return error if request is for not yet minted token
+            assert_eq!(rmrk.token_uri(42), Err(TokenNotExists));
+            assert_eq!(
+                rmrk.token_uri(1),
+                Ok(PreludeString::from(BASE_URI.to_owned() + "1.json"))
+            );
+
+            // return error if request is for not yet minted token
+            assert_eq!(rmrk.token_uri(42), Err(TokenNotExists));
+
+            // verify token_uri when baseUri is empty
+            set_sender(accounts.alice);
+            assert!(rmrk.set_base_uri(PreludeString::from("")).is_ok());
+            assert_eq!(
+                rmrk.token_uri(1),
+                Ok("".to_owned() + &PreludeString::from("1.json"))
+            );
+        }
+
+        #[ink::test]
+        fn owner_is_set() {
+            let accounts = default_accounts();
+            let rmrk = init();
+            assert_eq!(rmrk.owner(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn set_base_uri_works() {
+            let accounts = default_accounts();
+            const NEW_BASE_URI: &str = "new_uri/";
+            let mut rmrk = init();
+
+            set_sender(accounts.alice);
+            let collection_id = rmrk.collection_id();
+            assert!(rmrk.set_base_uri(NEW_BASE_URI.into()).is_ok());
+            assert_eq!(
+                rmrk.get_attribute(collection_id, String::from("baseUri")),
+                Some(String::from(NEW_BASE_URI))
+            );
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.set_base_uri(NEW_BASE_URI.into()),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+        }
+
+        #[ink::test]
+        fn check_supply_overflow_ok() {
+            let accounts = default_accounts();
+            let max_supply = u64::MAX - 1;
+            let mut rmrk = Rmrk::new(
+                String::from("Remark Project"),
+                String::from("RMK"),
+                String::from(BASE_URI),
+                max_supply,
+                PRICE,
+                String::from(BASE_URI),
+                accounts.eve,
+                0,
+            );
+            rmrk.minting.last_token_id = max_supply - 1;
+
+            // check case when last_token_id.add(mint_amount) if more than u64::MAX
+            assert_eq!(
+                rmrk._check_amount(3),
+                Err(PSP34Error::Custom(RmrkError::CollectionIsFull.as_str()))
+            );
+
+            // check case when mint_amount is 0
+            assert_eq!(
+                rmrk._check_amount(0),
+                Err(PSP34Error::Custom(RmrkError::CannotMintZeroTokens.as_str()))
+            );
+        }
+
+        #[ink::test]
+        fn check_value_overflow_ok() {
+            let accounts = default_accounts();
+            let max_supply = u64::MAX;
+            let price = u128::MAX as u128;
+            let rmrk = Rmrk::new(
                 String::from("Remark Project"),
                 String::from("RMK"),
                 String::from(BASE_URI),
@@ -629,19 +1811,122 @@ pub mod rmrk_contract {
             const ASSET_ID: AssetId = 1;
             let mut rmrk = init();
             assert!(rmrk
-                .add_asset_entry(ASSET_ID, 1, String::from(ASSET_URI))
+                .add_asset_entry(ASSET_ID, 1, None, None, String::from(ASSET_URI), vec![])
                 .is_ok());
             assert_eq!(rmrk.total_assets(), 1);
             assert_eq!(rmrk.get_asset_uri(ASSET_ID), Some(String::from(ASSET_URI)));
             assert_eq!(rmrk.get_asset_uri(42), None);
+            assert_eq!(rmrk.get_asset_equippable_group(ASSET_ID), Some(1));
+            assert_eq!(rmrk.get_asset_equippable_group(42), None);
 
             // reject adding asset with same asset_id
             assert_eq!(
-                rmrk.add_asset_entry(ASSET_ID, 1, String::from(ASSET_URI)),
+                rmrk.add_asset_entry(ASSET_ID, 1, None, None, String::from(ASSET_URI), vec![]),
                 Err(PSP34Error::Custom(RmrkError::AssetIdAlreadyExists.as_str()))
             );
         }
 
+        #[ink::test]
+        fn add_asset_entry_with_parts_works() {
+            const ASSET_URI: &str = "asset_uri/";
+            const ASSET_ID: AssetId = 1;
+            const BASE_ID: BaseId = 0;
+            const SLOT_ID: PartId = 0;
+            const FIXED_ID: PartId = 1;
+            let mut rmrk = init();
+            assert_eq!(
+                rmrk.add_base(String::from("ipfs://base_metadata")),
+                Ok(BASE_ID)
+            );
+            assert!(rmrk
+                .add_part_list(
+                    BASE_ID,
+                    vec![
+                        Part {
+                            part_type: PartType::Slot,
+                            z: 0,
+                            equippable: vec![],
+                            metadata_uri: String::from("ipfs://slots/1.svg"),
+                            is_equippable_by_all: true,
+                        },
+                        Part {
+                            part_type: PartType::Fixed,
+                            z: 1,
+                            equippable: vec![],
+                            metadata_uri: String::from("ipfs://fixed/1.svg"),
+                            is_equippable_by_all: false,
+                        },
+                    ]
+                )
+                .is_ok());
+
+            // parts must exist in the base
+            assert_eq!(
+                rmrk.add_asset_entry(
+                    ASSET_ID,
+                    0,
+                    None,
+                    Some(BASE_ID),
+                    String::from(ASSET_URI),
+                    vec![SLOT_ID, 42]
+                ),
+                Err(PSP34Error::Custom(RmrkError::UnknownPartId.as_str()))
+            );
+            // the base must be registered
+            assert_eq!(
+                rmrk.add_asset_entry(
+                    ASSET_ID,
+                    0,
+                    None,
+                    Some(42),
+                    String::from(ASSET_URI),
+                    vec![SLOT_ID]
+                ),
+                Err(PSP34Error::Custom(RmrkError::UnknownBaseId.as_str()))
+            );
+            // parts can't be used without a base
+            assert_eq!(
+                rmrk.add_asset_entry(
+                    ASSET_ID,
+                    0,
+                    None,
+                    None,
+                    String::from(ASSET_URI),
+                    vec![SLOT_ID]
+                ),
+                Err(PSP34Error::Custom(RmrkError::BadConfig.as_str()))
+            );
+            assert!(rmrk
+                .add_asset_entry(
+                    ASSET_ID,
+                    0,
+                    None,
+                    Some(BASE_ID),
+                    String::from(ASSET_URI),
+                    vec![SLOT_ID, FIXED_ID]
+                )
+                .is_ok());
+            assert_eq!(rmrk.get_asset_fixed_parts(ASSET_ID), Ok(vec![FIXED_ID]));
+            assert_eq!(rmrk.get_asset_slot_parts(ASSET_ID), Ok(vec![SLOT_ID]));
+            assert_eq!(
+                rmrk.get_asset_slot_parts(42),
+                Err(PSP34Error::Custom(RmrkError::AssetIdNotFound.as_str()))
+            );
+
+            // this collection can be used as the catalog of its own assets
+            assert!(rmrk
+                .add_asset_entry(
+                    ASSET_ID + 1,
+                    0,
+                    Some(rmrk.env().account_id()),
+                    Some(BASE_ID),
+                    String::from(ASSET_URI),
+                    vec![FIXED_ID]
+                )
+                .is_ok());
+            assert_eq!(rmrk.get_asset_fixed_parts(ASSET_ID + 1), Ok(vec![FIXED_ID]));
+        }
+
         #[ink::test]
         fn add_asset_to_token_works() {
             let accounts = default_accounts();
@@ -653,7 +1938,7 @@ pub mod rmrk_contract {
             let mut rmrk = init();
             // Add new asset entry
             assert!(rmrk
-                .add_asset_entry(ASSET_ID, 1, String::from(ASSET_URI))
+                .add_asset_entry(ASSET_ID, 1, None, None, String::from(ASSET_URI), vec![])
                 .is_ok());
             assert_eq!(rmrk.total_assets(), 1);
             assert_eq!(1, ink_env::test::recorded_events().count());
@@ -739,6 +2024,43 @@ pub mod rmrk_contract {
             assert_eq!(rmrk.total_token_assets(TOKEN_ID2), Ok((0, 0)));
         }
 
+        #[ink::test]
+        fn transfer_clears_pending_assets() {
+            let accounts = default_accounts();
+            const ASSET_ID1: AssetId = 1;
+            const ASSET_ID2: AssetId = 2;
+            const TOKEN_ID: Id = Id::U64(1);
+
+            let mut rmrk = init();
+            assert!(rmrk
+                .add_asset_entry(ASSET_ID1, 1, None, None, String::from("asset_uri/"), vec![])
+                .is_ok());
+            assert!(rmrk
+                .add_asset_entry(ASSET_ID2, 1, None, None, String::from("asset_uri/"), vec![])
+                .is_ok());
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE as u128);
+            assert!(rmrk.mint(accounts.bob, 1).is_ok());
+            assert!(rmrk.add_asset_to_token(TOKEN_ID, ASSET_ID1, None).is_ok());
+            assert!(rmrk.add_asset_to_token(TOKEN_ID, ASSET_ID2, None).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk.accept_asset(TOKEN_ID, ASSET_ID1).is_ok());
+            assert_eq!(rmrk.total_token_assets(TOKEN_ID), Ok((1, 1)));
+
+            // new owner receives only the accepted assets
+            assert!(psp34::Internal::_before_token_transfer(
+                &mut rmrk,
+                Some(&accounts.bob),
+                Some(&accounts.charlie),
+                &TOKEN_ID
+            )
+            .is_ok());
+            assert_eq!(rmrk.total_token_assets(TOKEN_ID), Ok((1, 0)));
+            assert_eq!(
+                rmrk.get_accepted_token_assets(TOKEN_ID),
+                Ok(Some(vec![ASSET_ID1]))
+            );
+        }
+
         #[ink::test]
         fn set_asset_priority_works() {
             let accounts = default_accounts();
@@ -750,10 +2072,10 @@ pub mod rmrk_contract {
             let mut rmrk = init();
             // Add new asset entry
             assert!(rmrk
-                .add_asset_entry(ASSET_ID1, 1, String::from(ASSET_URI))
+                .add_asset_entry(ASSET_ID1, 1, None, None, String::from(ASSET_URI), vec![])
                 .is_ok());
             assert!(rmrk
-                .add_asset_entry(ASSET_ID2, 1, String::from(ASSET_URI))
+                .add_asset_entry(ASSET_ID2, 1, None, None, String::from(ASSET_URI), vec![])
                 .is_ok());
             assert_eq!(rmrk.total_assets(), 2);
 
@@ -795,6 +2117,7 @@ pub mod rmrk_contract {
             const EQUIPABLE_ADDRESS1: [u8; 32] = [1; 32];
             const EQUIPABLE_ADDRESS2: [u8; 32] = [2; 32];
             const EQUIPABLE_ADDRESS3: [u8; 32] = [3; 32];
+            const BASE_ID: BaseId = 0;
             const PART_ID0: PartId = 0;
             const PART_ID1: PartId = 1;
             let part_list = vec![
@@ -832,68 +2155,518 @@ pub mod rmrk_contract {
             }];
 
             let mut rmrk = init();
+            assert_eq!(rmrk.add_base(String::from("")), Ok(BASE_ID));
 
             // verify add/get parts
-            assert!(rmrk.get_parts_count() == 0);
-            assert!(rmrk.add_part_list(part_list.clone()).is_ok());
-            assert_eq!(rmrk.get_parts_count(), part_list.len() as u32);
-            assert_eq!(rmrk.get_part(0).unwrap().z, part_list[0].z);
+            assert!(rmrk.get_parts_count(BASE_ID) == 0);
+            assert!(rmrk.add_part_list(BASE_ID, part_list.clone()).is_ok());
+            assert_eq!(rmrk.get_parts_count(BASE_ID), part_list.len() as u32);
+            assert_eq!(rmrk.get_part(BASE_ID, 0).unwrap().z, part_list[0].z);
             assert_eq!(
-                rmrk.get_part(0).unwrap().metadata_uri,
+                rmrk.get_part(BASE_ID, 0).unwrap().metadata_uri,
                 part_list[0].metadata_uri
             );
 
             // verify array of equippable addresses
-            assert!(rmrk.is_equippable(PART_ID0, EQUIPABLE_ADDRESS1.into()));
-            assert!(rmrk.is_equippable(PART_ID0, EQUIPABLE_ADDRESS2.into()));
-            assert!(!rmrk.is_equippable(PART_ID1, EQUIPABLE_ADDRESS2.into()));
-
-            assert!(!rmrk.is_equippable_by_all(PART_ID0));
-            assert!(rmrk.set_equippable_by_all(PART_ID0).is_ok());
-            assert!(rmrk.is_equippable_by_all(PART_ID0));
-            assert!(!rmrk.is_equippable_by_all(42));
-
-            assert!(rmrk.reset_equippable_addresses(PART_ID0).is_ok());
-            assert!(!rmrk.is_equippable_by_all(PART_ID0));
-            assert!(!rmrk.is_equippable(PART_ID0, EQUIPABLE_ADDRESS1.into()));
+            assert!(rmrk.is_equippable(BASE_ID, PART_ID0, EQUIPABLE_ADDRESS1.into()));
+            assert!(rmrk.is_equippable(BASE_ID, PART_ID0, EQUIPABLE_ADDRESS2.into()));
+            assert!(!rmrk.is_equippable(BASE_ID, PART_ID1, EQUIPABLE_ADDRESS2.into()));
+
+            assert!(!rmrk.is_equippable_by_all(BASE_ID, PART_ID0));
+            assert!(rmrk.set_equippable_by_all(BASE_ID, PART_ID0).is_ok());
+            assert!(rmrk.is_equippable_by_all(BASE_ID, PART_ID0));
+            assert!(!rmrk.is_equippable_by_all(BASE_ID, 42));
+
+            assert!(rmrk.reset_equippable_addresses(BASE_ID, PART_ID0).is_ok());
+            assert!(!rmrk.is_equippable_by_all(BASE_ID, PART_ID0));
+            assert!(!rmrk.is_equippable(BASE_ID, PART_ID0, EQUIPABLE_ADDRESS1.into()));
             assert!(rmrk
                 .add_equippable_addresses(
+                    BASE_ID,
                     PART_ID0,
                     vec![EQUIPABLE_ADDRESS1.into(), EQUIPABLE_ADDRESS2.into()]
                 )
                 .is_ok());
-            assert!(rmrk.is_equippable(PART_ID0, EQUIPABLE_ADDRESS1.into()));
+            assert!(rmrk.is_equippable(BASE_ID, PART_ID0, EQUIPABLE_ADDRESS1.into()));
             assert_eq!(
-                rmrk.add_equippable_addresses(PART_ID1, vec![EQUIPABLE_ADDRESS1.into()]),
+                rmrk.add_equippable_addresses(BASE_ID, PART_ID1, vec![EQUIPABLE_ADDRESS1.into()]),
                 Err(PSP34Error::Custom(RmrkError::PartIsNotSlot.as_str()))
             );
             assert_eq!(
-                rmrk.reset_equippable_addresses(PART_ID1),
+                rmrk.reset_equippable_addresses(BASE_ID, PART_ID1),
                 Err(PSP34Error::Custom(RmrkError::PartIsNotSlot.as_str()))
             );
             assert_eq!(
-                rmrk.set_equippable_by_all(PART_ID1),
+                rmrk.set_equippable_by_all(BASE_ID, PART_ID1),
                 Err(PSP34Error::Custom(RmrkError::PartIsNotSlot.as_str()))
             );
             assert_eq!(
-                rmrk.add_part_list(bad_part_list1.clone()),
+                rmrk.add_part_list(BASE_ID, bad_part_list1.clone()),
                 Err(PSP34Error::Custom(RmrkError::BadConfig.as_str()))
             );
             assert_eq!(
-                rmrk.add_part_list(bad_part_list2.clone()),
+                rmrk.add_part_list(BASE_ID, bad_part_list2.clone()),
                 Err(PSP34Error::Custom(RmrkError::BadConfig.as_str()))
             );
 
-            assert!(!rmrk.is_equippable(PART_ID0, EQUIPABLE_ADDRESS3.into()));
+            assert!(!rmrk.is_equippable(BASE_ID, PART_ID0, EQUIPABLE_ADDRESS3.into()));
 
             // verify set/get base metadata
-            assert_eq!(rmrk.get_base_metadata(), "");
+            assert_eq!(rmrk.get_base_metadata(BASE_ID), "");
+            assert!(rmrk
+                .setup_base(BASE_ID, String::from("ipfs://base_metadata"))
+                .is_ok());
+            assert_eq!(rmrk.get_base_metadata(BASE_ID), "ipfs://base_metadata");
+
+            assert_eq!(7, ink_env::test::recorded_events().count());
+        }
+
+        #[ink::test]
+        fn update_and_retire_parts_works() {
+            let accounts = default_accounts();
+            const EQUIPABLE_ADDRESS1: [u8; 32] = [1; 32];
+            const EQUIPABLE_ADDRESS2: [u8; 32] = [2; 32];
+            const BASE_ID: BaseId = 0;
+            const SLOT_ID: PartId = 0;
+            const FIXED_ID: PartId = 1;
+            let mut rmrk = init();
+            assert_eq!(rmrk.add_base(String::from("")), Ok(BASE_ID));
+            assert!(rmrk
+                .add_part_list(
+                    BASE_ID,
+                    vec![
+                        Part {
+                            part_type: PartType::Slot,
+                            z: 0,
+                            equippable: vec![EQUIPABLE_ADDRESS1.into(), EQUIPABLE_ADDRESS2.into()],
+                            metadata_uri: String::from("ipfs://slots/1.svg"),
+                            is_equippable_by_all: false,
+                        },
+                        Part {
+                            part_type: PartType::Fixed,
+                            z: 1,
+                            equippable: vec![],
+                            metadata_uri: String::from("ipfs://fixed/1.svg"),
+                            is_equippable_by_all: false,
+                        },
+                    ]
+                )
+                .is_ok());
+
+            // update part
+            assert!(rmrk
+                .update_part(BASE_ID, FIXED_ID, 5, String::from("ipfs://fixed/2.svg"))
+                .is_ok());
+            let part = rmrk.get_part(BASE_ID, FIXED_ID).unwrap();
+            assert_eq!(part.z, 5);
+            assert_eq!(part.metadata_uri, String::from("ipfs://fixed/2.svg"));
+            assert_eq!(
+                rmrk.update_part(BASE_ID, 42, 5, String::from("ipfs://fixed/2.svg")),
+                Err(PSP34Error::Custom(RmrkError::UnknownPartId.as_str()))
+            );
+
+            // remove single equippable address
+            assert!(rmrk
+                .remove_equippable_address(BASE_ID, SLOT_ID, EQUIPABLE_ADDRESS1.into())
+                .is_ok());
+            assert!(!rmrk.is_equippable(BASE_ID, SLOT_ID, EQUIPABLE_ADDRESS1.into()));
+            assert!(rmrk.is_equippable(BASE_ID, SLOT_ID, EQUIPABLE_ADDRESS2.into()));
+            assert_eq!(
+                rmrk.remove_equippable_address(BASE_ID, SLOT_ID, EQUIPABLE_ADDRESS1.into()),
+                Err(PSP34Error::Custom(
+                    RmrkError::EquippableAddressNotFound.as_str()
+                ))
+            );
+            assert_eq!(
+                rmrk.remove_equippable_address(BASE_ID, FIXED_ID, EQUIPABLE_ADDRESS1.into()),
+                Err(PSP34Error::Custom(RmrkError::PartIsNotSlot.as_str()))
+            );
+
+            // retire part
+            assert!(!rmrk.is_part_retired(BASE_ID, SLOT_ID));
+            assert!(rmrk.retire_part(BASE_ID, SLOT_ID).is_ok());
+            assert!(rmrk.is_part_retired(BASE_ID, SLOT_ID));
+            assert_eq!(
+                rmrk.retire_part(BASE_ID, SLOT_ID),
+                Err(PSP34Error::Custom(RmrkError::PartIsRetired.as_str()))
+            );
+            assert_eq!(
+                rmrk.retire_part(BASE_ID, 42),
+                Err(PSP34Error::Custom(RmrkError::UnknownPartId.as_str()))
+            );
+            assert_eq!(
+                rmrk.update_part(BASE_ID, SLOT_ID, 5, String::from("ipfs://slots/2.svg")),
+                Err(PSP34Error::Custom(RmrkError::PartIsRetired.as_str()))
+            );
+            assert_eq!(
+                rmrk.set_equippable_by_all(BASE_ID, SLOT_ID),
+                Err(PSP34Error::Custom(RmrkError::PartIsRetired.as_str()))
+            );
+
+            // new assets can't reference the retired part
+            assert_eq!(
+                rmrk.add_asset_entry(
+                    1,
+                    0,
+                    None,
+                    Some(BASE_ID),
+                    String::from("asset_uri/"),
+                    vec![SLOT_ID, FIXED_ID]
+                ),
+                Err(PSP34Error::Custom(RmrkError::PartIsRetired.as_str()))
+            );
+
+            // only owner can modify parts
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.retire_part(BASE_ID, FIXED_ID),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+        }
+
+        #[ink::test]
+        fn lock_base_works() {
+            let accounts = default_accounts();
+            const BASE_ID: BaseId = 0;
+            const SLOT_ID: PartId = 0;
+            let part_list = vec![Part {
+                part_type: PartType::Slot,
+                z: 0,
+                equippable: vec![],
+                metadata_uri: String::from("ipfs://slots/1.svg"),
+                is_equippable_by_all: false,
+            }];
+            let mut rmrk = init();
+            assert_eq!(rmrk.add_base(String::from("")), Ok(BASE_ID));
+            assert!(rmrk.add_part_list(BASE_ID, part_list.clone()).is_ok());
+
+            // only owner can lock
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.lock_base(BASE_ID),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            set_sender(accounts.alice);
+            assert!(!rmrk.is_locked(BASE_ID));
+            assert!(rmrk.lock_base(BASE_ID).is_ok());
+            assert!(rmrk.is_locked(BASE_ID));
+            assert_eq!(3, ink_env::test::recorded_events().count());
+
+            // nothing can be changed on a locked base
+            let locked = Err(PSP34Error::Custom(RmrkError::BaseIsLocked.as_str()));
+            assert_eq!(rmrk.lock_base(BASE_ID), locked);
+            assert_eq!(rmrk.add_part_list(BASE_ID, part_list), locked);
+            assert_eq!(
+                rmrk.add_equippable_addresses(BASE_ID, SLOT_ID, vec![accounts.bob]),
+                locked
+            );
+            assert_eq!(rmrk.reset_equippable_addresses(BASE_ID, SLOT_ID), locked);
+            assert_eq!(rmrk.set_equippable_by_all(BASE_ID, SLOT_ID), locked);
+            assert_eq!(
+                rmrk.setup_base(BASE_ID, String::from("ipfs://base_metadata")),
+                locked
+            );
+            assert_eq!(
+                rmrk.update_part(BASE_ID, SLOT_ID, 1, String::from("ipfs://slots/2.svg")),
+                locked
+            );
+            assert_eq!(rmrk.retire_part(BASE_ID, SLOT_ID), locked);
+            assert_eq!(rmrk.get_parts_count(BASE_ID), 1);
+        }
+
+        #[ink::test]
+        fn multiple_bases_works() {
+            const BASE_ID1: BaseId = 0;
+            const BASE_ID2: BaseId = 1;
+            let part = Part {
+                part_type: PartType::Fixed,
+                z: 0,
+                equippable: vec![],
+                metadata_uri: String::from("ipfs://fixed/1.svg"),
+                is_equippable_by_all: false,
+            };
+            let mut rmrk = init();
+            assert_eq!(rmrk.get_bases(), vec![]);
+            assert_eq!(
+                rmrk.add_part_list(BASE_ID1, vec![part.clone()]),
+                Err(PSP34Error::Custom(RmrkError::UnknownBaseId.as_str()))
+            );
+
+            assert_eq!(rmrk.add_base(String::from("ipfs://base/1")), Ok(BASE_ID1));
+            assert_eq!(rmrk.add_base(String::from("ipfs://base/2")), Ok(BASE_ID2));
+            assert_eq!(rmrk.get_bases(), vec![BASE_ID1, BASE_ID2]);
+            assert_eq!(rmrk.get_base_metadata(BASE_ID2), "ipfs://base/2");
+
+            // every base has its own part ids
+            assert!(rmrk
+                .add_part_list(BASE_ID1, vec![part.clone(), part.clone()])
+                .is_ok());
+            assert!(rmrk.add_part_list(BASE_ID2, vec![part.clone()]).is_ok());
+            assert_eq!(rmrk.get_parts_count(BASE_ID1), 2);
+            assert_eq!(rmrk.get_parts_count(BASE_ID2), 1);
+            assert!(rmrk.get_part(BASE_ID2, 0).is_some());
+            assert!(rmrk.get_part(BASE_ID2, 1).is_none());
+
+            // locking one base leaves the other one untouched
+            assert!(rmrk.lock_base(BASE_ID1).is_ok());
+            assert!(!rmrk.is_locked(BASE_ID2));
+            assert!(rmrk.retire_part(BASE_ID2, 0).is_ok());
+            assert!(!rmrk.is_part_retired(BASE_ID1, 0));
+
+            // only owner can add a base
+            set_sender(default_accounts().bob);
+            assert_eq!(
+                rmrk.add_base(String::from("ipfs://base/3")),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+        }
+
+        #[ink::test]
+        fn equip_works() {
+            let accounts = default_accounts();
+            const ASSET_URI: &str = "asset_uri/";
+            const ASSET_ID: AssetId = 1;
+            const EQUIPPABLE_GROUP_ID: EquippableGroupId = 1;
+            const BASE_ID: BaseId = 0;
+            const PARENT_ID: Id = Id::U64(1);
+            const CHILD_ID: Id = Id::U64(2);
+            const SLOT_ID: SlotId = 0;
+            const FIXED_ID: SlotId = 1;
+            let mut rmrk = init();
+            let child_nft = (rmrk.env().account_id(), CHILD_ID);
+
+            // one slot equippable by this collection and one fixed part
+            assert_eq!(
+                rmrk.add_base(String::from("ipfs://base_metadata")),
+                Ok(BASE_ID)
+            );
+            assert!(rmrk
+                .add_part_list(
+                    BASE_ID,
+                    vec![
+                        Part {
+                            part_type: PartType::Slot,
+                            z: 0,
+                            equippable: vec![rmrk.env().account_id()],
+                            metadata_uri: String::from("ipfs://slots/1.svg"),
+                            is_equippable_by_all: false,
+                        },
+                        Part {
+                            part_type: PartType::Fixed,
+                            z: 1,
+                            equippable: vec![],
+                            metadata_uri: String::from("ipfs://fixed/1.svg"),
+                            is_equippable_by_all: false,
+                        },
+                    ]
+                )
+                .is_ok());
+            assert!(rmrk
+                .add_asset_entry(
+                    ASSET_ID,
+                    EQUIPPABLE_GROUP_ID,
+                    None,
+                    Some(BASE_ID),
+                    String::from(ASSET_URI),
+                    vec![SLOT_ID, FIXED_ID]
+                )
+                .is_ok());
+            assert!(rmrk
+                .add_asset_entry(
+                    ASSET_ID + 1,
+                    EQUIPPABLE_GROUP_ID,
+                    None,
+                    None,
+                    String::from(ASSET_URI),
+                    vec![]
+                )
+                .is_ok());
+
+            // mint parent and child to Bob, parent has the asset accepted
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE * 2 as u128);
+            assert!(rmrk.mint(accounts.bob, 2).is_ok());
+            assert!(rmrk.add_asset_to_token(PARENT_ID, ASSET_ID, None).is_ok());
+            assert!(rmrk
+                .add_asset_to_token(PARENT_ID, ASSET_ID + 1, None)
+                .is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk.accept_asset(PARENT_ID, ASSET_ID).is_ok());
+            assert!(rmrk.accept_asset(PARENT_ID, ASSET_ID + 1).is_ok());
+
+            // child is not nested yet
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(RmrkError::ChildNotFound.as_str()))
+            );
+
+            // nest the child directly, add_child needs a cross contract call
+            rmrk.nesting
+                .accepted_children
+                .insert(&PARENT_ID, &vec![child_nft.clone()]);
+
+            // only parent owner can equip
+            set_sender(accounts.charlie);
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(RmrkError::NotAuthorised.as_str()))
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.equip(PARENT_ID, 42, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(RmrkError::AssetIdNotFound.as_str()))
+            );
+            assert_eq!(
+                rmrk.equip(
+                    PARENT_ID,
+                    ASSET_ID + 1,
+                    SLOT_ID,
+                    child_nft.clone(),
+                    ASSET_ID
+                ),
+                Err(PSP34Error::Custom(
+                    RmrkError::TargetAssetCannotReceiveSlot.as_str()
+                ))
+            );
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, FIXED_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(RmrkError::PartIsNotSlot.as_str()))
+            );
+            assert!(!rmrk.is_child_equipped(child_nft.clone()));
+
+            // child doesn't have the asset accepted
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(
+                    RmrkError::ChildAssetNotAccepted.as_str()
+                ))
+            );
+            set_sender(accounts.alice);
+            assert!(rmrk.add_asset_to_token(CHILD_ID, ASSET_ID, None).is_ok());
+            set_sender(accounts.bob);
+            assert!(rmrk.accept_asset(CHILD_ID, ASSET_ID).is_ok());
+
+            // equippable group of the child asset is not allowed into the slot yet
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(
+                    RmrkError::ChildAssetNotEquippable.as_str()
+                ))
+            );
+            assert_eq!(
+                rmrk.set_valid_parent_for_equippable_group(
+                    EQUIPPABLE_GROUP_ID,
+                    rmrk.env().account_id(),
+                    BASE_ID,
+                    SLOT_ID
+                ),
+                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+            );
+            set_sender(accounts.alice);
+            assert!(rmrk
+                .set_valid_parent_for_equippable_group(
+                    EQUIPPABLE_GROUP_ID,
+                    rmrk.env().account_id(),
+                    BASE_ID,
+                    SLOT_ID
+                )
+                .is_ok());
+            assert!(rmrk.is_valid_parent_for_equippable_group(
+                EQUIPPABLE_GROUP_ID,
+                rmrk.env().account_id(),
+                BASE_ID,
+                SLOT_ID
+            ));
+            assert!(!rmrk.is_valid_parent_for_equippable_group(
+                EQUIPPABLE_GROUP_ID,
+                rmrk.env().account_id(),
+                BASE_ID,
+                FIXED_ID
+            ));
+            // the same slot id in another Base is a different slot
+            assert!(!rmrk.is_valid_parent_for_equippable_group(
+                EQUIPPABLE_GROUP_ID,
+                rmrk.env().account_id(),
+                BASE_ID + 1,
+                SLOT_ID
+            ));
+
+            set_sender(accounts.bob);
             assert!(rmrk
-                .setup_base(String::from("ipfs://base_metadata"))
+                .equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID)
                 .is_ok());
-            assert_eq!(rmrk.get_base_metadata(), "ipfs://base_metadata");
+            assert!(rmrk.is_child_equipped(child_nft.clone()));
+            assert_eq!(
+                rmrk.get_equipment(PARENT_ID, ASSET_ID, SLOT_ID),
+                Some(Equipment {
+                    child_nft: child_nft.clone(),
+                    child_asset_id: ASSET_ID,
+                })
+            );
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft.clone(), ASSET_ID),
+                Err(PSP34Error::Custom(RmrkError::SlotAlreadyUsed.as_str()))
+            );
+
+            // composition includes the fixed part and the equipped child
+            assert_eq!(
+                rmrk.compose(PARENT_ID, ASSET_ID),
+                Ok(Composition {
+                    base_metadata_uri: String::from("ipfs://base_metadata"),
+                    asset_uri: String::from(ASSET_URI),
+                    fixed_parts: vec![FixedPart {
+                        part_id: FIXED_ID,
+                        z: 1,
+                        metadata_uri: String::from("ipfs://fixed/1.svg"),
+                    }],
+                    slot_parts: vec![SlotPart {
+                        part_id: SLOT_ID,
+                        z: 0,
+                        metadata_uri: String::from("ipfs://slots/1.svg"),
+                        equipment: Some(Equipment {
+                            child_nft: child_nft.clone(),
+                            child_asset_id: ASSET_ID,
+                        }),
+                        child_asset_uri: Some(String::from(ASSET_URI)),
+                    }],
+                })
+            );
+            assert_eq!(
+                rmrk.compose(PARENT_ID, 42),
+                Err(PSP34Error::Custom(RmrkError::AssetIdNotFound.as_str()))
+            );
+
+            // equipped child can't leave the parent
+            test::set_value_transferred::<ink_env::DefaultEnvironment>(PRICE);
+            assert!(rmrk.mint(accounts.bob, 1).is_ok());
+            assert_eq!(
+                rmrk.remove_child(PARENT_ID, child_nft.clone()),
+                Err(PSP34Error::Custom(RmrkError::MustUnequipFirst.as_str()))
+            );
+            assert_eq!(
+                rmrk.transfer_child(PARENT_ID, Id::U64(3), child_nft.clone()),
+                Err(PSP34Error::Custom(RmrkError::MustUnequipFirst.as_str()))
+            );
+
+            // unequip
+            assert!(rmrk.unequip(PARENT_ID, ASSET_ID, SLOT_ID).is_ok());
+            assert!(!rmrk.is_child_equipped(child_nft.clone()));
+            assert_eq!(rmrk.get_equipment(PARENT_ID, ASSET_ID, SLOT_ID), None);
+            assert_eq!(
+                rmrk.unequip(PARENT_ID, ASSET_ID, SLOT_ID),
+                Err(PSP34Error::Custom(RmrkError::NotEquipped.as_str()))
+            );
 
-            // assert_eq!(1, ink_env::test::recorded_events().count());
+            // collection not allowed to be equipped into the slot
+            set_sender(accounts.alice);
+            assert!(rmrk.reset_equippable_addresses(BASE_ID, SLOT_ID).is_ok());
+            set_sender(accounts.bob);
+            assert_eq!(
+                rmrk.equip(PARENT_ID, ASSET_ID, SLOT_ID, child_nft, ASSET_ID),
+                Err(PSP34Error::Custom(
+                    RmrkError::CollectionNotEquippable.as_str()
+                ))
+            );
         }
 
         fn default_accounts() -> test::DefaultAccounts<ink_env::DefaultEnvironment> {
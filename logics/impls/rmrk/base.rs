@@ -1,11 +1,16 @@
 //! RMRK Base implementation
 
 use crate::impls::rmrk::{
-    errors::RmrkError,
+    errors::{
+        child_contract_error,
+        RmrkError,
+    },
     types::*,
 };
 pub use crate::traits::base::{
     Base,
+    BaseEvents,
+    BaseRef,
     Internal,
 };
 use ink_prelude::{
@@ -30,8 +35,32 @@ impl<T> Internal for T
 where
     T: Storage<BaseData>,
 {
-    default fn ensure_only_slot(&self, part_id: PartId) -> Result<Part, PSP34Error> {
-        if let Some(part) = self.data::<BaseData>().parts.get(part_id) {
+    default fn create_base(&mut self, base_metadata: String) -> BaseId {
+        let base_id = self.data::<BaseData>().next_base_id;
+        self.data::<BaseData>()
+            .base_metadata_uri
+            .insert(base_id, &base_metadata);
+        self.data::<BaseData>().next_base_id += 1;
+        self._emit_base_added_event(&base_id, &base_metadata);
+
+        base_id
+    }
+
+    default fn ensure_base_exists(&self, base_id: BaseId) -> Result<(), PSP34Error> {
+        if base_id >= self.data::<BaseData>().next_base_id {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::UnknownBaseId.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    default fn ensure_only_slot(
+        &self,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<Part, PSP34Error> {
+        if let Some(part) = self.data::<BaseData>().parts.get(&(base_id, part_id)) {
             if part.part_type != PartType::Slot {
                 return Err(PSP34Error::Custom(String::from(
                     RmrkError::PartIsNotSlot.as_str(),
@@ -44,17 +73,134 @@ where
             )))
         }
     }
+
+    default fn ensure_not_locked(&self, base_id: BaseId) -> Result<(), PSP34Error> {
+        self.ensure_base_exists(base_id)?;
+        if self.data::<BaseData>().locked.get(base_id).unwrap_or(false) {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::BaseIsLocked.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    default fn ensure_not_retired(
+        &self,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<(), PSP34Error> {
+        if self
+            .data::<BaseData>()
+            .retired_parts
+            .get(&(base_id, part_id))
+            .unwrap_or(false)
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::PartIsRetired.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    default fn catalog_base_exists(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+    ) -> Result<bool, PSP34Error> {
+        match catalog_address {
+            Some(catalog) if catalog != Self::env().account_id() => {
+                BaseRef::get_bases_builder(&catalog)
+                    .fire()
+                    .map(|bases| bases.contains(&base_id))
+                    .map_err(child_contract_error)
+            }
+            _ => Ok(self.ensure_base_exists(base_id).is_ok()),
+        }
+    }
+
+    default fn get_catalog_part(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<Option<Part>, PSP34Error> {
+        match catalog_address {
+            Some(catalog) if catalog != Self::env().account_id() => {
+                BaseRef::get_part_builder(&catalog, base_id, part_id)
+                    .fire()
+                    .map_err(child_contract_error)
+            }
+            _ => Ok(self.data::<BaseData>().parts.get(&(base_id, part_id))),
+        }
+    }
+
+    default fn is_catalog_part_retired(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<bool, PSP34Error> {
+        match catalog_address {
+            Some(catalog) if catalog != Self::env().account_id() => {
+                BaseRef::is_part_retired_builder(&catalog, base_id, part_id)
+                    .fire()
+                    .map_err(child_contract_error)
+            }
+            _ => Ok(self.ensure_not_retired(base_id, part_id).is_err()),
+        }
+    }
+
+    default fn get_catalog_metadata(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+    ) -> Result<String, PSP34Error> {
+        match catalog_address {
+            Some(catalog) if catalog != Self::env().account_id() => {
+                BaseRef::get_base_metadata_builder(&catalog, base_id)
+                    .fire()
+                    .map(|metadata| metadata.into_bytes())
+                    .map_err(child_contract_error)
+            }
+            _ => {
+                Ok(self
+                    .data::<BaseData>()
+                    .base_metadata_uri
+                    .get(base_id)
+                    .unwrap_or_default())
+            }
+        }
+    }
 }
 impl<T> Base for T
 where
     T: Storage<BaseData> + Storage<ownable::Data>,
 {
+    /// Register a new Base with its own parts and metadata
+    #[modifiers(only_owner)]
+    default fn add_base(&mut self, base_metadata: String) -> Result<BaseId, PSP34Error> {
+        Ok(self.create_base(base_metadata))
+    }
+
     /// Add one or more parts to the base
     #[modifiers(only_owner)]
-    default fn add_part_list(&mut self, parts: Vec<Part>) -> Result<(), PSP34Error> {
+    default fn add_part_list(
+        &mut self,
+        base_id: BaseId,
+        parts: Vec<Part>,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        let mut part_ids = self
+            .data::<BaseData>()
+            .part_ids
+            .get(base_id)
+            .unwrap_or_default();
+        let mut part_id = self
+            .data::<BaseData>()
+            .next_part_id
+            .get(base_id)
+            .unwrap_or_default();
         for part in parts {
-            let part_id = self.data::<BaseData>().next_part_id;
-
             if part.part_type == PartType::Fixed
                 && (part.equippable.len() != 0 || part.is_equippable_by_all)
             {
@@ -62,10 +208,17 @@ where
                     RmrkError::BadConfig.as_str(),
                 )))
             }
-            self.data::<BaseData>().parts.insert(part_id, &part);
-            self.data::<BaseData>().part_ids.push(part_id);
-            self.data::<BaseData>().next_part_id += 1;
+            self.data::<BaseData>()
+                .parts
+                .insert(&(base_id, part_id), &part);
+            part_ids.push(part_id);
+            self._emit_part_added_event(&base_id, &part_id, &part);
+            part_id += 1;
         }
+        self.data::<BaseData>().part_ids.insert(base_id, &part_ids);
+        self.data::<BaseData>()
+            .next_part_id
+            .insert(base_id, &part_id);
 
         Ok(())
     }
@@ -74,66 +227,202 @@ where
     #[modifiers(only_owner)]
     default fn add_equippable_addresses(
         &mut self,
+        base_id: BaseId,
         part_id: PartId,
         equippable_address: Vec<AccountId>,
     ) -> Result<(), PSP34Error> {
-        let mut part = self.ensure_only_slot(part_id)?;
-        part.equippable.extend(equippable_address);
-        self.data::<BaseData>().parts.insert(part_id, &part);
+        self.ensure_not_locked(base_id)?;
+        let mut part = self.ensure_only_slot(base_id, part_id)?;
+        self.ensure_not_retired(base_id, part_id)?;
+        part.equippable.extend(equippable_address.iter());
+        self.data::<BaseData>()
+            .parts
+            .insert(&(base_id, part_id), &part);
+        self._emit_equippable_addresses_added_event(&base_id, &part_id, &equippable_address);
 
         Ok(())
     }
 
     /// Remove list of equippable addresses for given Part
     #[modifiers(only_owner)]
-    default fn reset_equippable_addresses(&mut self, part_id: PartId) -> Result<(), PSP34Error> {
-        let mut part = self.ensure_only_slot(part_id)?;
+    default fn reset_equippable_addresses(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        let mut part = self.ensure_only_slot(base_id, part_id)?;
         part.is_equippable_by_all = false;
         part.equippable.clear();
-        self.data::<BaseData>().parts.insert(part_id, &part);
+        self.data::<BaseData>()
+            .parts
+            .insert(&(base_id, part_id), &part);
+        self._emit_equippable_addresses_reset_event(&base_id, &part_id);
+
+        Ok(())
+    }
+
+    /// Remove a single collection address from the equippable list of given `PartId`.
+    #[modifiers(only_owner)]
+    default fn remove_equippable_address(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+        equippable_address: AccountId,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        let mut part = self.ensure_only_slot(base_id, part_id)?;
+        let index = part
+            .equippable
+            .iter()
+            .position(|a| *a == equippable_address)
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::EquippableAddressNotFound.as_str(),
+            )))?;
+        part.equippable.remove(index);
+        self.data::<BaseData>()
+            .parts
+            .insert(&(base_id, part_id), &part);
+        self._emit_equippable_address_removed_event(&base_id, &part_id, &equippable_address);
+
+        Ok(())
+    }
+
+    /// Update the depth and the metadataURI of the given `PartId`.
+    #[modifiers(only_owner)]
+    default fn update_part(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+        z: u8,
+        metadata_uri: String,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        let mut part = self
+            .data::<BaseData>()
+            .parts
+            .get(&(base_id, part_id))
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::UnknownPartId.as_str(),
+            )))?;
+        self.ensure_not_retired(base_id, part_id)?;
+        part.z = z;
+        part.metadata_uri = metadata_uri;
+        self.data::<BaseData>()
+            .parts
+            .insert(&(base_id, part_id), &part);
+        self._emit_part_updated_event(&base_id, &part_id, &part);
+
+        Ok(())
+    }
+
+    /// Retire the given `PartId` so it can't be referenced by new assets or equipped into.
+    #[modifiers(only_owner)]
+    default fn retire_part(&mut self, base_id: BaseId, part_id: PartId) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        if self
+            .data::<BaseData>()
+            .parts
+            .get(&(base_id, part_id))
+            .is_none()
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::UnknownPartId.as_str(),
+            )))
+        }
+        self.ensure_not_retired(base_id, part_id)?;
+        self.data::<BaseData>()
+            .retired_parts
+            .insert(&(base_id, part_id), &true);
+        self._emit_part_retired_event(&base_id, &part_id);
 
         Ok(())
     }
 
     /// Sets the is_equippable_by_all flag to true, meaning that any collection may be equipped into the `PartId`
     #[modifiers(only_owner)]
-    default fn set_equippable_by_all(&mut self, part_id: PartId) -> Result<(), PSP34Error> {
-        let mut part = self.ensure_only_slot(part_id)?;
+    default fn set_equippable_by_all(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        let mut part = self.ensure_only_slot(base_id, part_id)?;
+        self.ensure_not_retired(base_id, part_id)?;
         part.is_equippable_by_all = true;
-        self.data::<BaseData>().parts.insert(part_id, &part);
+        self.data::<BaseData>()
+            .parts
+            .insert(&(base_id, part_id), &part);
+        self._emit_equippable_to_all_set_event(&base_id, &part_id);
 
         Ok(())
     }
 
     /// Sets the metadata URI for Base
     #[modifiers(only_owner)]
-    default fn setup_base(&mut self, base_metadata: String) -> Result<(), PSP34Error> {
-        self.data::<BaseData>().base_metadata_uri = base_metadata;
+    default fn setup_base(
+        &mut self,
+        base_id: BaseId,
+        base_metadata: String,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        self.data::<BaseData>()
+            .base_metadata_uri
+            .insert(base_id, &base_metadata);
+        self._emit_base_metadata_set_event(&base_id, &base_metadata);
 
         Ok(())
     }
 
+    /// Lock the Base so its parts and metadata can never change again
+    #[modifiers(only_owner)]
+    default fn lock_base(&mut self, base_id: BaseId) -> Result<(), PSP34Error> {
+        self.ensure_not_locked(base_id)?;
+        self.data::<BaseData>().locked.insert(base_id, &true);
+        self._emit_base_locked_event(&base_id);
+
+        Ok(())
+    }
+
+    /// Get the list of all Bases.
+    default fn get_bases(&self) -> Vec<BaseId> {
+        (0..self.data::<BaseData>().next_base_id).collect()
+    }
+
     /// Get the Base metadataURI.
-    default fn get_base_metadata(&self) -> PreludeString {
-        match PreludeString::from_utf8(self.data::<BaseData>().base_metadata_uri.clone()) {
+    default fn get_base_metadata(&self, base_id: BaseId) -> PreludeString {
+        let base_metadata = self
+            .data::<BaseData>()
+            .base_metadata_uri
+            .get(base_id)
+            .unwrap_or_default();
+        match PreludeString::from_utf8(base_metadata) {
             Ok(m) => m,
             _ => PreludeString::from(""),
         }
     }
 
     /// Get the number of parts.
-    default fn get_parts_count(&self) -> PartId {
-        self.data::<BaseData>().next_part_id
+    default fn get_parts_count(&self, base_id: BaseId) -> PartId {
+        self.data::<BaseData>()
+            .next_part_id
+            .get(base_id)
+            .unwrap_or_default()
     }
 
     /// Get the part details for the given PartId.
-    default fn get_part(&self, part_id: PartId) -> Option<Part> {
-        self.data::<BaseData>().parts.get(part_id)
+    default fn get_part(&self, base_id: BaseId, part_id: PartId) -> Option<Part> {
+        self.data::<BaseData>().parts.get(&(base_id, part_id))
     }
 
     /// Check whether the given address is allowed to equip the desired `PartId`.
-    default fn is_equippable(&self, part_id: PartId, target_address: AccountId) -> bool {
-        if let Some(part) = self.data::<BaseData>().parts.get(part_id) {
+    default fn is_equippable(
+        &self,
+        base_id: BaseId,
+        part_id: PartId,
+        target_address: AccountId,
+    ) -> bool {
+        if let Some(part) = self.data::<BaseData>().parts.get(&(base_id, part_id)) {
             if part.equippable.contains(&target_address) {
                 return true
             }
@@ -143,11 +432,71 @@ where
     }
 
     /// Checks if the given `PartId` can be equipped by any collection
-    default fn is_equippable_by_all(&self, part_id: PartId) -> bool {
-        if let Some(part) = self.data::<BaseData>().parts.get(part_id) {
+    default fn is_equippable_by_all(&self, base_id: BaseId, part_id: PartId) -> bool {
+        if let Some(part) = self.data::<BaseData>().parts.get(&(base_id, part_id)) {
             return part.is_equippable_by_all
         }
 
         return false
     }
+
+    /// Checks if the given `PartId` is retired
+    default fn is_part_retired(&self, base_id: BaseId, part_id: PartId) -> bool {
+        self.ensure_not_retired(base_id, part_id).is_err()
+    }
+
+    /// Checks if the Base is locked
+    default fn is_locked(&self, base_id: BaseId) -> bool {
+        self.data::<BaseData>().locked.get(base_id).unwrap_or(false)
+    }
+}
+
+/// Event trait for Base
+impl<T> BaseEvents for T
+where
+    T: Storage<BaseData>,
+{
+    /// Used to notify listeners that a new Base is registered.
+    default fn _emit_base_added_event(&self, _base_id: &BaseId, _base_metadata: &String) {}
+
+    /// Used to notify listeners that a part is added to the Base.
+    default fn _emit_part_added_event(&self, _base_id: &BaseId, _part_id: &PartId, _part: &Part) {}
+
+    /// Used to notify listeners that collection addresses are allowed to be equipped into the part.
+    default fn _emit_equippable_addresses_added_event(
+        &self,
+        _base_id: &BaseId,
+        _part_id: &PartId,
+        _equippable_addresses: &[AccountId],
+    ) {
+    }
+
+    /// Used to notify listeners that a collection address is removed from the equippable list.
+    default fn _emit_equippable_address_removed_event(
+        &self,
+        _base_id: &BaseId,
+        _part_id: &PartId,
+        _equippable_address: &AccountId,
+    ) {
+    }
+
+    /// Used to notify listeners that the equippable list of the part is cleared.
+    default fn _emit_equippable_addresses_reset_event(&self, _base_id: &BaseId, _part_id: &PartId) {
+    }
+
+    /// Used to notify listeners that any collection can be equipped into the part.
+    default fn _emit_equippable_to_all_set_event(&self, _base_id: &BaseId, _part_id: &PartId) {}
+
+    /// Used to notify listeners that the depth or the metadataURI of the part changed.
+    default fn _emit_part_updated_event(&self, _base_id: &BaseId, _part_id: &PartId, _part: &Part) {
+    }
+
+    /// Used to notify listeners that the part is retired.
+    default fn _emit_part_retired_event(&self, _base_id: &BaseId, _part_id: &PartId) {}
+
+    /// Used to notify listeners that the Base metadataURI is set.
+    default fn _emit_base_metadata_set_event(&self, _base_id: &BaseId, _base_metadata: &String) {}
+
+    /// Used to notify listeners that the Base is locked.
+    default fn _emit_base_locked_event(&self, _base_id: &BaseId) {}
 }
@@ -0,0 +1,535 @@
+//! This module enables equipping of nested children into the Base slots of the parent's assets.
+
+pub use crate::traits::equippable::{
+    Equippable,
+    EquippableEvents,
+    Internal,
+};
+use crate::{
+    impls::rmrk::{
+        errors::{
+            child_contract_error,
+            RmrkError,
+        },
+        types::*,
+    },
+    traits::{
+        base::Internal as BaseInternal,
+        equippable::EquippableRef,
+        multiasset::MultiAssetRef,
+        nesting::Nesting,
+    },
+};
+use ink_prelude::vec::Vec;
+use openbrush::{
+    contracts::{
+        ownable::*,
+        psp34::extensions::enumerable::*,
+    },
+    modifiers,
+    traits::{
+        AccountId,
+        Storage,
+        String,
+    },
+};
+
+/// Implement internal helper trait for Equippable
+impl<T> Internal for T
+where
+    T: Storage<EquippableData>
+        + Storage<BaseData>
+        + Storage<MultiAssetData>
+        + Storage<NestingData>
+        + Storage<psp34::Data<enumerable::Balances>>
+        + Nesting,
+{
+    /// Check if the caller is the root owner of the parent token
+    default fn ensure_parent_owner(&self, token_id: &Id) -> Result<(), PSP34Error> {
+        if self.root_owner_of(token_id.clone())? != Self::env().caller() {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::NotAuthorised.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    /// Check if the asset is accepted by the parent token
+    default fn ensure_parent_asset(
+        &self,
+        token_id: &Id,
+        asset_id: &AssetId,
+    ) -> Result<(), PSP34Error> {
+        if let Some(assets) = self.data::<MultiAssetData>().accepted_assets.get(token_id) {
+            if assets.contains(asset_id) {
+                return Ok(())
+            }
+        }
+        Err(PSP34Error::Custom(String::from(
+            RmrkError::AssetIdNotFound.as_str(),
+        )))
+    }
+
+    /// Check if the slot is one of the parts composing the parent's asset
+    default fn ensure_asset_slot(
+        &self,
+        asset_id: &AssetId,
+        slot_part_id: SlotId,
+    ) -> Result<Asset, PSP34Error> {
+        let asset = self
+            .data::<MultiAssetData>()
+            .collection_asset_entries
+            .iter()
+            .find(|a| a.asset_id == *asset_id)
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::AssetIdNotFound.as_str(),
+            )))?;
+        if !asset.part_ids.contains(&slot_part_id) {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::TargetAssetCannotReceiveSlot.as_str(),
+            )))
+        }
+        Ok(asset.clone())
+    }
+
+    /// Check if the child is accepted by the parent token
+    default fn ensure_accepted_child(
+        &self,
+        token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        if let Some(children) = self.data::<NestingData>().accepted_children.get(token_id) {
+            if children.contains(child_nft) {
+                return Ok(())
+            }
+        }
+        Err(PSP34Error::Custom(String::from(
+            RmrkError::ChildNotFound.as_str(),
+        )))
+    }
+
+    /// Check if the child collection can be equipped into the slot of the asset's Base
+    default fn ensure_equippable_slot(
+        &self,
+        asset: &Asset,
+        slot_part_id: SlotId,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        let catalog_address = asset.catalog_address;
+        let base_id = asset.base_id.unwrap_or_default();
+        let part = self
+            .get_catalog_part(catalog_address, base_id, slot_part_id)?
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::UnknownPartId.as_str(),
+            )))?;
+        if part.part_type != PartType::Slot {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::PartIsNotSlot.as_str(),
+            )))
+        }
+        if self.is_catalog_part_retired(catalog_address, base_id, slot_part_id)? {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::PartIsRetired.as_str(),
+            )))
+        }
+        if !part.is_equippable_by_all && !part.equippable.contains(&child_nft.0) {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::CollectionNotEquippable.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    /// Check if the child is not equipped. Return error if it is
+    default fn ensure_not_equipped(&self, child_nft: &ChildNft) -> Result<(), PSP34Error> {
+        if self
+            .data::<EquippableData>()
+            .equipped_children
+            .get(child_nft)
+            .is_some()
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::MustUnequipFirst.as_str(),
+            )))
+        }
+        Ok(())
+    }
+
+    /// Get the uri of the equipped child's asset
+    default fn get_child_asset_uri(
+        &self,
+        equipment: &Equipment,
+    ) -> Result<Option<String>, PSP34Error> {
+        let (collection, _) = &equipment.child_nft;
+        if *collection == Self::env().account_id() {
+            return Ok(self
+                .data::<MultiAssetData>()
+                .collection_asset_entries
+                .iter()
+                .find(|a| a.asset_id == equipment.child_asset_id)
+                .map(|a| a.asset_uri.clone()))
+        }
+
+        MultiAssetRef::get_asset_uri_builder(collection, equipment.child_asset_id)
+            .fire()
+            .map_err(child_contract_error)
+    }
+
+    /// Check if the child has the asset accepted and get the equippable group of the asset.
+    /// Children of other collections are queried through their MultiAsset messages
+    default fn get_child_asset_group(
+        &self,
+        child_nft: &ChildNft,
+        child_asset_id: &AssetId,
+    ) -> Result<EquippableGroupId, PSP34Error> {
+        let (collection, child_token_id) = child_nft;
+        let (accepted_assets, equippable_group_id) = if *collection == Self::env().account_id() {
+            let group = self
+                .data::<MultiAssetData>()
+                .collection_asset_entries
+                .iter()
+                .find(|a| a.asset_id == *child_asset_id)
+                .map(|a| a.equippable_group_id);
+            (
+                self.data::<MultiAssetData>()
+                    .accepted_assets
+                    .get(child_token_id),
+                group,
+            )
+        } else {
+            let accepted_assets = MultiAssetRef::get_accepted_token_assets_builder(
+                collection,
+                child_token_id.clone(),
+            )
+            .fire()
+            .map_err(child_contract_error)??;
+            let group =
+                MultiAssetRef::get_asset_equippable_group_builder(collection, *child_asset_id)
+                    .fire()
+                    .map_err(child_contract_error)?;
+            (accepted_assets, group)
+        };
+
+        if !accepted_assets.unwrap_or_default().contains(child_asset_id) {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::ChildAssetNotAccepted.as_str(),
+            )))
+        }
+        equippable_group_id.ok_or(PSP34Error::Custom(String::from(
+            RmrkError::AssetIdNotFound.as_str(),
+        )))
+    }
+
+    /// Check if the equippable group of the child asset allows it to be equipped into the slot.
+    /// The child collection declares which parents and slots its equippable groups are valid for
+    default fn ensure_equippable_group(
+        &self,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+        child_nft: &ChildNft,
+        child_asset_id: &AssetId,
+    ) -> Result<(), PSP34Error> {
+        let equippable_group_id = self.get_child_asset_group(child_nft, child_asset_id)?;
+        let this = Self::env().account_id();
+        let is_valid_parent = if child_nft.0 == this {
+            self.data::<EquippableData>().valid_parent_slots.get(&(
+                equippable_group_id,
+                this,
+                base_id,
+            )) == Some(slot_part_id)
+        } else {
+            EquippableRef::is_valid_parent_for_equippable_group_builder(
+                &child_nft.0,
+                equippable_group_id,
+                this,
+                base_id,
+                slot_part_id,
+            )
+            .fire()
+            .map_err(child_contract_error)?
+        };
+        if !is_valid_parent {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::ChildAssetNotEquippable.as_str(),
+            )))
+        }
+        Ok(())
+    }
+}
+
+impl<T> Equippable for T
+where
+    T: Storage<EquippableData>
+        + Storage<BaseData>
+        + Storage<MultiAssetData>
+        + Storage<NestingData>
+        + Storage<psp34::Data<enumerable::Balances>>
+        + Storage<ownable::Data>
+        + Nesting,
+{
+    /// Used to equip a child nft into a token.
+    default fn equip(
+        &mut self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+        child_nft: ChildNft,
+        child_asset_id: AssetId,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_parent_owner(&token_id)?;
+        self.ensure_parent_asset(&token_id, &asset_id)?;
+        let asset = self.ensure_asset_slot(&asset_id, slot_part_id)?;
+        self.ensure_accepted_child(&token_id, &child_nft)?;
+        self.ensure_equippable_slot(&asset, slot_part_id, &child_nft)?;
+        self.ensure_equippable_group(
+            asset.base_id.unwrap_or_default(),
+            slot_part_id,
+            &child_nft,
+            &child_asset_id,
+        )?;
+
+        let slot = (token_id.clone(), asset_id, slot_part_id);
+        if self.data::<EquippableData>().equipment.get(&slot).is_some() {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::SlotAlreadyUsed.as_str(),
+            )))
+        }
+        if self
+            .data::<EquippableData>()
+            .equipped_children
+            .get(&child_nft)
+            .is_some()
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::ChildAlreadyEquipped.as_str(),
+            )))
+        }
+
+        self.data::<EquippableData>().equipment.insert(
+            &slot,
+            &Equipment {
+                child_nft: child_nft.clone(),
+                child_asset_id,
+            },
+        );
+        self.data::<EquippableData>()
+            .equipped_children
+            .insert(&child_nft, &slot);
+        self._emit_child_asset_equipped_event(
+            &token_id,
+            &asset_id,
+            &slot_part_id,
+            &child_nft,
+            &child_asset_id,
+        );
+
+        Ok(())
+    }
+
+    /// Used to unequip child from parent token.
+    default fn unequip(
+        &mut self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_parent_owner(&token_id)?;
+
+        let slot = (token_id.clone(), asset_id, slot_part_id);
+        let equipment =
+            self.data::<EquippableData>()
+                .equipment
+                .get(&slot)
+                .ok_or(PSP34Error::Custom(String::from(
+                    RmrkError::NotEquipped.as_str(),
+                )))?;
+
+        self.data::<EquippableData>().equipment.remove(&slot);
+        self.data::<EquippableData>()
+            .equipped_children
+            .remove(&equipment.child_nft);
+        self._emit_child_asset_unequipped_event(&token_id, &asset_id, &slot_part_id);
+
+        Ok(())
+    }
+
+    /// Used to unequip the child and remove it from the parent token in one call.
+    default fn unequip_and_remove(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<(), PSP34Error> {
+        if let Some((token_id, asset_id, slot_part_id)) = self
+            .data::<EquippableData>()
+            .equipped_children
+            .get(&child_nft)
+        {
+            if token_id != parent_token_id {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::ChildNotFound.as_str(),
+                )))
+            }
+            self.unequip(token_id, asset_id, slot_part_id)?;
+        }
+        self.remove_child(parent_token_id, child_nft)
+    }
+
+    /// Used to retrieve the child equipped into the slot of the token's asset.
+    default fn get_equipment(
+        &self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+    ) -> Option<Equipment> {
+        self.data::<EquippableData>()
+            .equipment
+            .get(&(token_id, asset_id, slot_part_id))
+    }
+
+    /// Check whether the child is equipped into any slot of its parent.
+    default fn is_child_equipped(&self, child_nft: ChildNft) -> bool {
+        self.data::<EquippableData>()
+            .equipped_children
+            .get(&child_nft)
+            .is_some()
+    }
+
+    /// Used to compose the layers of the token's asset for rendering.
+    default fn compose(&self, token_id: Id, asset_id: AssetId) -> Result<Composition, PSP34Error> {
+        self.ensure_parent_asset(&token_id, &asset_id)?;
+        let asset = self
+            .data::<MultiAssetData>()
+            .collection_asset_entries
+            .iter()
+            .find(|a| a.asset_id == asset_id)
+            .cloned()
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::AssetIdNotFound.as_str(),
+            )))?;
+
+        let base_id = asset.base_id.unwrap_or_default();
+        let mut fixed_parts = Vec::new();
+        let mut slot_parts = Vec::new();
+        for part_id in asset.part_ids {
+            let part = match self.get_catalog_part(asset.catalog_address, base_id, part_id)? {
+                Some(part) => part,
+                None => continue,
+            };
+            match part.part_type {
+                PartType::Fixed => {
+                    fixed_parts.push(FixedPart {
+                        part_id,
+                        z: part.z,
+                        metadata_uri: part.metadata_uri,
+                    })
+                }
+                PartType::Slot => {
+                    let equipment = self.data::<EquippableData>().equipment.get(&(
+                        token_id.clone(),
+                        asset_id,
+                        part_id,
+                    ));
+                    let child_asset_uri = match &equipment {
+                        Some(equipment) => self.get_child_asset_uri(equipment)?,
+                        None => None,
+                    };
+                    slot_parts.push(SlotPart {
+                        part_id,
+                        z: part.z,
+                        metadata_uri: part.metadata_uri,
+                        equipment,
+                        child_asset_uri,
+                    })
+                }
+                PartType::None => {}
+            }
+        }
+        fixed_parts.sort_by_key(|p| p.z);
+        slot_parts.sort_by_key(|p| p.z);
+
+        let base_metadata_uri = match asset.base_id {
+            Some(base_id) => self.get_catalog_metadata(asset.catalog_address, base_id)?,
+            None => String::default(),
+        };
+        Ok(Composition {
+            base_metadata_uri,
+            asset_uri: asset.asset_uri,
+            fixed_parts,
+            slot_parts,
+        })
+    }
+
+    /// Used to declare that the assets belonging to a given `equippable_group_id` are equippable into the `Slot`
+    #[modifiers(only_owner)]
+    default fn set_valid_parent_for_equippable_group(
+        &mut self,
+        equippable_group_id: EquippableGroupId,
+        parent_address: AccountId,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+    ) -> Result<(), PSP34Error> {
+        self.data::<EquippableData>().valid_parent_slots.insert(
+            &(equippable_group_id, parent_address, base_id),
+            &slot_part_id,
+        );
+        self._emit_valid_parent_equippable_group_id_set_event(
+            &equippable_group_id,
+            &parent_address,
+            &base_id,
+            &slot_part_id,
+        );
+
+        Ok(())
+    }
+
+    /// Check whether the assets of the `equippable_group_id` can be equipped into the slot of the parent
+    default fn is_valid_parent_for_equippable_group(
+        &self,
+        equippable_group_id: EquippableGroupId,
+        parent_address: AccountId,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+    ) -> bool {
+        self.data::<EquippableData>().valid_parent_slots.get(&(
+            equippable_group_id,
+            parent_address,
+            base_id,
+        )) == Some(slot_part_id)
+    }
+}
+
+/// Event trait for Equippable
+impl<T> EquippableEvents for T
+where
+    T: Storage<EquippableData>,
+{
+    /// Used to notify listeners that a child's asset has been equipped into one of its parent assets.
+    default fn _emit_child_asset_equipped_event(
+        &self,
+        _token_id: &Id,
+        _asset_id: &AssetId,
+        _slot_part_id: &SlotId,
+        _child_nft: &ChildNft,
+        _child_asset_id: &AssetId,
+    ) {
+    }
+
+    /// Used to notify listeners that a child's asset has been unequipped from one of its parent assets.
+    default fn _emit_child_asset_unequipped_event(
+        &self,
+        _token_id: &Id,
+        _asset_id: &AssetId,
+        _slot_part_id: &SlotId,
+    ) {
+    }
+
+    /// Used to notify listeners that the assets belonging to a `equippable_group_id` have been marked as equippable into a given slot and parent
+    default fn _emit_valid_parent_equippable_group_id_set_event(
+        &self,
+        _equippable_group_id: &EquippableGroupId,
+        _parent_address: &AccountId,
+        _base_id: &BaseId,
+        _slot_part_id: &SlotId,
+    ) {
+    }
+}
@@ -1,6 +1,9 @@
 //! Error definition for RMRK contract
 
-use openbrush::traits::String;
+use openbrush::{
+    contracts::psp34::PSP34Error,
+    traits::String,
+};
 
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -24,6 +27,28 @@ pub enum RmrkError {
     UnknownPartId,
     PartIsNotSlot,
     BadConfig,
+    SlotAlreadyUsed,
+    CollectionNotEquippable,
+    ChildAlreadyEquipped,
+    NotEquipped,
+    ChildAssetNotEquippable,
+    TargetAssetCannotReceiveSlot,
+    MustUnequipFirst,
+    ChildAssetNotAccepted,
+    PartIsRetired,
+    EquippableAddressNotFound,
+    BaseIsLocked,
+    UnknownBaseId,
+    MaxRecursiveBurnsReached,
+    ChildContractNotApproved,
+    NestingCycleDetected,
+    MaxNestingDepthExceeded,
+    MaxAcceptedChildrenReached,
+    MaxPendingChildrenReached,
+    MaxRejectionsReached,
+    ChildContractCallFailed,
+    ChildContractNotPsp34,
+    MaxBatchChildrenExceeded,
 }
 
 impl RmrkError {
@@ -48,6 +73,40 @@ impl RmrkError {
             RmrkError::UnknownPartId => String::from("UnknownPartId"),
             RmrkError::PartIsNotSlot => String::from("PartIsNotSlot"),
             RmrkError::BadConfig => String::from("BadConfig"),
+            RmrkError::SlotAlreadyUsed => String::from("SlotAlreadyUsed"),
+            RmrkError::CollectionNotEquippable => String::from("CollectionNotEquippable"),
+            RmrkError::ChildAlreadyEquipped => String::from("ChildAlreadyEquipped"),
+            RmrkError::NotEquipped => String::from("NotEquipped"),
+            RmrkError::ChildAssetNotEquippable => String::from("ChildAssetNotEquippable"),
+            RmrkError::TargetAssetCannotReceiveSlot => String::from("TargetAssetCannotReceiveSlot"),
+            RmrkError::MustUnequipFirst => String::from("MustUnequipFirst"),
+            RmrkError::ChildAssetNotAccepted => String::from("ChildAssetNotAccepted"),
+            RmrkError::PartIsRetired => String::from("PartIsRetired"),
+            RmrkError::EquippableAddressNotFound => String::from("EquippableAddressNotFound"),
+            RmrkError::BaseIsLocked => String::from("BaseIsLocked"),
+            RmrkError::UnknownBaseId => String::from("UnknownBaseId"),
+            RmrkError::MaxRecursiveBurnsReached => String::from("MaxRecursiveBurnsReached"),
+            RmrkError::ChildContractNotApproved => String::from("ChildContractNotApproved"),
+            RmrkError::NestingCycleDetected => String::from("NestingCycleDetected"),
+            RmrkError::MaxNestingDepthExceeded => String::from("MaxNestingDepthExceeded"),
+            RmrkError::MaxAcceptedChildrenReached => String::from("MaxAcceptedChildrenReached"),
+            RmrkError::MaxPendingChildrenReached => String::from("MaxPendingChildrenReached"),
+            RmrkError::MaxRejectionsReached => String::from("MaxRejectionsReached"),
+            RmrkError::ChildContractCallFailed => String::from("ChildContractCallFailed"),
+            RmrkError::ChildContractNotPsp34 => String::from("ChildContractNotPsp34"),
+            RmrkError::MaxBatchChildrenExceeded => String::from("MaxBatchChildrenExceeded"),
         }
     }
 }
+
+/// Map the failure of a cross contract call to a child contract
+pub fn child_contract_error(error: ink_env::Error) -> PSP34Error {
+    let rmrk_error = match error {
+        // There is no contract at the address or its reply is not the expected PSP34 type
+        ink_env::Error::CodeNotFound | ink_env::Error::NotCallable | ink_env::Error::Decode(_) => {
+            RmrkError::ChildContractNotPsp34
+        }
+        _ => RmrkError::ChildContractCallFailed,
+    };
+    PSP34Error::Custom(String::from(rmrk_error.as_str()))
+}
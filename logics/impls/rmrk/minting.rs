@@ -1,13 +1,19 @@
 //! RMRK minting implementation
 
-use crate::impls::rmrk::{
-    errors::RmrkError,
-    types::MintingData,
-};
 pub use crate::traits::minting::{
     Internal,
     Minting,
 };
+use crate::{
+    impls::rmrk::{
+        errors::RmrkError,
+        types::{
+            MintingData,
+            NestingData,
+        },
+    },
+    traits::nesting::Internal as NestingInternal,
+};
 use openbrush::{
     contracts::{
         ownable::*,
@@ -28,6 +34,7 @@ use openbrush::{
 impl<T> Minting for T
 where
     T: Storage<MintingData>
+        + Storage<NestingData>
         + Storage<psp34::Data<enumerable::Balances>>
         + Storage<reentrancy_guard::Data>
         + Storage<ownable::Data>
@@ -72,6 +79,60 @@ where
 
         Ok(())
     }
+
+    /// Mint one or more tokens directly into a parent token
+    #[modifiers(non_reentrant)]
+    default fn nest_mint(
+        &mut self,
+        parent_collection: AccountId,
+        parent_token_id: Id,
+        mint_amount: u64,
+    ) -> Result<(), PSP34Error> {
+        self._check_value(Self::env().transferred_value(), mint_amount)?;
+        self._check_amount(mint_amount)?;
+
+        let this = Self::env().account_id();
+        let next_to_mint = self.data::<MintingData>().last_token_id + 1; // first mint id is 1
+        let accepted = if parent_collection == this {
+            let parent_owner = self.ensure_exists(&parent_token_id)?;
+            self.ensure_valid_nesting(&parent_token_id, &(this, Id::U64(next_to_mint)))?;
+            let accepted = Self::env().caller() == parent_owner;
+            self.ensure_children_capacity(&parent_token_id, accepted, mint_amount as usize)?;
+            Some(accepted)
+        } else {
+            self.ensure_remote_parent(parent_collection, &parent_token_id, mint_amount)?;
+            None
+        };
+
+        // Tokens for another collection go to the caller first, so the parent records it as sender
+        let caller = Self::env().caller();
+        let token_owner = if accepted.is_some() { this } else { caller };
+        let mint_offset = next_to_mint + mint_amount;
+
+        for mint_id in next_to_mint..mint_offset {
+            self.data::<psp34::Data<enumerable::Balances>>()
+                ._mint_to(token_owner, Id::U64(mint_id))?;
+            self.data::<MintingData>().last_token_id += 1;
+            self._emit_transfer_event(None, Some(token_owner), Id::U64(mint_id));
+
+            if let Some(accepted) = accepted {
+                self.nest_child(
+                    parent_token_id.clone(),
+                    (this, Id::U64(mint_id)),
+                    accepted,
+                    caller,
+                )?;
+            } else {
+                self.nest_into_collection(
+                    parent_collection,
+                    parent_token_id.clone(),
+                    Id::U64(mint_id),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Helper trait for Minting
@@ -1,14 +1,20 @@
 //! This module enables multiasset capability of RMRK
 
-use crate::impls::rmrk::{
-    errors::RmrkError,
-    types::*,
-};
 pub use crate::traits::multiasset::{
     Internal,
     MultiAsset,
     MultiAssetEvents,
 };
+use crate::{
+    impls::rmrk::{
+        errors::RmrkError,
+        types::*,
+    },
+    traits::{
+        base::Internal as BaseInternal,
+        nesting::Nesting,
+    },
+};
 use ink_prelude::vec::Vec;
 use openbrush::{
     contracts::{
@@ -26,7 +32,10 @@ use openbrush::{
 /// Implement internal helper trait for MultiAsset
 impl<T> Internal for T
 where
-    T: Storage<MultiAssetData> + Storage<psp34::Data<enumerable::Balances>>,
+    T: Storage<MultiAssetData>
+        + Storage<BaseData>
+        + Storage<psp34::Data<enumerable::Balances>>
+        + Nesting,
 {
     /// Check if token is minted. Return the token uri
     default fn asset_id_exists(&self, asset_id: AssetId) -> Option<String> {
@@ -44,6 +53,77 @@ where
         None
     }
 
+    /// Get the asset entry for the given asset_id
+    default fn get_asset_entry(&self, asset_id: AssetId) -> Result<Asset, PSP34Error> {
+        self.data::<MultiAssetData>()
+            .collection_asset_entries
+            .iter()
+            .find(|a| a.asset_id == asset_id)
+            .cloned()
+            .ok_or(PSP34Error::Custom(String::from(
+                RmrkError::AssetIdNotFound.as_str(),
+            )))
+    }
+
+    /// Check if the Base is registered and all the parts exist in it and are not retired
+    default fn ensure_asset_parts(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: Option<BaseId>,
+        part_ids: &[PartId],
+    ) -> Result<(), PSP34Error> {
+        let base_id = match base_id {
+            Some(base_id) => base_id,
+            None if part_ids.is_empty() => return Ok(()),
+            None => {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::BadConfig.as_str(),
+                )))
+            }
+        };
+        if !self.catalog_base_exists(catalog_address, base_id)? {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::UnknownBaseId.as_str(),
+            )))
+        }
+        for part_id in part_ids {
+            if self
+                .get_catalog_part(catalog_address, base_id, *part_id)?
+                .is_none()
+            {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::UnknownPartId.as_str(),
+                )))
+            }
+            if self.is_catalog_part_retired(catalog_address, base_id, *part_id)? {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::PartIsRetired.as_str(),
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the parts of the given type composing the asset
+    default fn get_asset_parts_of_type(
+        &self,
+        asset_id: AssetId,
+        part_type: PartType,
+    ) -> Result<Vec<PartId>, PSP34Error> {
+        let asset = self.get_asset_entry(asset_id)?;
+        let catalog_address = asset.catalog_address;
+        let base_id = asset.base_id.unwrap_or_default();
+        let mut part_ids = Vec::new();
+        for part_id in asset.part_ids {
+            if let Some(part) = self.get_catalog_part(catalog_address, base_id, part_id)? {
+                if part.part_type == part_type {
+                    part_ids.push(part_id);
+                }
+            }
+        }
+        Ok(part_ids)
+    }
+
     /// Check if token is minted. Return the owner
     default fn ensure_exists(&self, id: &Id) -> Result<AccountId, PSP34Error> {
         let token_owner = self
@@ -53,10 +133,10 @@ where
         Ok(token_owner)
     }
 
-    /// Ensure that the caller is the token owner
-    default fn ensure_token_owner(&self, token_owner: AccountId) -> Result<(), PSP34Error> {
+    /// Ensure that the caller is the root owner of the token
+    default fn ensure_token_owner(&self, token_id: &Id) -> Result<(), PSP34Error> {
         let caller = Self::env().caller();
-        if caller != token_owner {
+        if caller != self.root_owner_of(token_id.clone())? {
             return Err(PSP34Error::Custom(String::from(
                 RmrkError::NotAuthorised.as_str(),
             )))
@@ -208,13 +288,30 @@ where
 
         Ok(())
     }
+
+    /// Remove all accepted and pending assets of the token
+    default fn remove_token_assets(&mut self, token_id: &Id) {
+        self.data::<MultiAssetData>()
+            .accepted_assets
+            .remove(token_id);
+        self.remove_pending_assets(token_id);
+    }
+
+    /// Remove all pending assets of the token
+    default fn remove_pending_assets(&mut self, token_id: &Id) {
+        self.data::<MultiAssetData>()
+            .pending_assets
+            .remove(token_id);
+    }
 }
 
 impl<T> MultiAsset for T
 where
     T: Storage<MultiAssetData>
+        + Storage<BaseData>
         + Storage<psp34::Data<enumerable::Balances>>
-        + Storage<ownable::Data>,
+        + Storage<ownable::Data>
+        + Nesting,
 {
     /// Used to add a asset entry.
     #[modifiers(only_owner)]
@@ -222,19 +319,26 @@ where
         &mut self,
         asset_id: AssetId,
         equippable_group_id: EquippableGroupId,
+        catalog_address: Option<AccountId>,
+        base_id: Option<BaseId>,
         asset_uri: String,
+        part_ids: Vec<PartId>,
     ) -> Result<(), PSP34Error> {
         if self.asset_id_exists(asset_id).is_some() {
             return Err(PSP34Error::Custom(String::from(
                 RmrkError::AssetIdAlreadyExists.as_str(),
             )))
         };
+        self.ensure_asset_parts(catalog_address, base_id, &part_ids)?;
         self.data::<MultiAssetData>()
             .collection_asset_entries
             .push(Asset {
                 asset_id,
                 equippable_group_id,
                 asset_uri,
+                catalog_address,
+                base_id,
+                part_ids,
             });
         self._emit_asset_set_event(&asset_id);
 
@@ -253,13 +357,12 @@ where
             .ok_or(PSP34Error::Custom(String::from(
                 RmrkError::AssetIdNotFound.as_str(),
             )))?;
-        let token_owner = self.ensure_exists(&token_id)?;
+        self.ensure_exists(&token_id)?;
         self.ensure_not_accepted(&token_id, &asset_id)?;
         self.ensure_not_pending(&token_id, &asset_id)?;
 
         self._emit_asset_added_to_token_event(&token_id, &asset_id, None);
-        let caller = Self::env().caller();
-        if caller == token_owner {
+        if self.ensure_token_owner(&token_id).is_ok() {
             self.add_to_accepted_assets(&token_id, &asset_id);
         } else {
             self.add_to_pending_assets(&token_id, &asset_id);
@@ -271,24 +374,19 @@ where
     /// Accepts an asset from the pending array of given token.
     fn accept_asset(&mut self, token_id: Id, asset_id: AssetId) -> Result<(), PSP34Error> {
         self.ensure_pending(&token_id, &asset_id)?;
-        let token_owner = self.ensure_exists(&token_id)?;
-        let caller = Self::env().caller();
-        if caller == token_owner {
-            self.remove_from_pending_assets(&token_id, &asset_id)?;
-            self.add_to_accepted_assets(&token_id, &asset_id);
-        } else {
-            return Err(PSP34Error::Custom(String::from(
-                RmrkError::NotAuthorised.as_str(),
-            )))
-        }
+        self.ensure_exists(&token_id)?;
+        self.ensure_token_owner(&token_id)?;
+
+        self.remove_from_pending_assets(&token_id, &asset_id)?;
+        self.add_to_accepted_assets(&token_id, &asset_id);
         Ok(())
     }
 
     /// Rejects an asset from the pending array of given token.
     fn reject_asset(&mut self, token_id: Id, asset_id: AssetId) -> Result<(), PSP34Error> {
         self.ensure_pending(&token_id, &asset_id)?;
-        let token_owner = self.ensure_exists(&token_id)?;
-        self.ensure_token_owner(token_owner)?;
+        self.ensure_exists(&token_id)?;
+        self.ensure_token_owner(&token_id)?;
 
         self.remove_from_pending_assets(&token_id, &asset_id)?;
 
@@ -299,8 +397,8 @@ where
     /// Remove an asset from the pending array of given token.
     fn remove_asset(&mut self, token_id: Id, asset_id: AssetId) -> Result<(), PSP34Error> {
         self.ensure_accepted(&token_id, &asset_id)?;
-        let token_owner = self.ensure_exists(&token_id)?;
-        self.ensure_token_owner(token_owner)?;
+        self.ensure_exists(&token_id)?;
+        self.ensure_token_owner(&token_id)?;
 
         self.remove_from_accepted_assets(&token_id, &asset_id)?;
 
@@ -310,8 +408,8 @@ where
 
     /// Used to specify the priorities for a given token's active assets.
     fn set_priority(&mut self, token_id: Id, priorities: Vec<AssetId>) -> Result<(), PSP34Error> {
-        let token_owner = self.ensure_exists(&token_id)?;
-        self.ensure_token_owner(token_owner)?;
+        self.ensure_exists(&token_id)?;
+        self.ensure_token_owner(&token_id)?;
         if let Some(accepted_assets) = self
             .data::<MultiAssetData>()
             .accepted_assets
@@ -372,6 +470,23 @@ where
         self.ensure_exists(&token_id)?;
         Ok(self.data::<MultiAssetData>().accepted_assets.get(&token_id))
     }
+
+    /// Used to retrieve the equippable group the asset belongs to
+    fn get_asset_equippable_group(&self, asset_id: AssetId) -> Option<EquippableGroupId> {
+        self.get_asset_entry(asset_id)
+            .ok()
+            .map(|asset| asset.equippable_group_id)
+    }
+
+    /// Used to retrieve the fixed parts composing the asset
+    fn get_asset_fixed_parts(&self, asset_id: AssetId) -> Result<Vec<PartId>, PSP34Error> {
+        self.get_asset_parts_of_type(asset_id, PartType::Fixed)
+    }
+
+    /// Used to retrieve the slot parts composing the asset
+    fn get_asset_slot_parts(&self, asset_id: AssetId) -> Result<Vec<PartId>, PSP34Error> {
+        self.get_asset_parts_of_type(asset_id, PartType::Slot)
+    }
 }
 
 /// Event trait for MultiAssets
@@ -1,18 +1,31 @@
 //! This module enables nesting of RMRK or any other NFT which inherits PSP34.
 
-use crate::impls::rmrk::{
-    errors::RmrkError,
-    types::*,
-};
 pub use crate::traits::nesting::{
     Internal,
     Nesting,
     NestingEvents,
 };
+use crate::{
+    impls::rmrk::{
+        errors::{
+            child_contract_error,
+            RmrkError,
+        },
+        types::*,
+    },
+    traits::nesting::NestingRef,
+};
 use ink_env::CallFlags;
 use ink_prelude::vec::Vec;
 use openbrush::{
-    contracts::psp34::extensions::enumerable::*,
+    contracts::{
+        ownable::*,
+        psp34::{
+            extensions::enumerable::*,
+            BalancesManager,
+        },
+    },
+    modifiers,
     traits::{
         AccountId,
         Storage,
@@ -23,7 +36,7 @@ use openbrush::{
 /// Implement internal helper trait for Nesting
 impl<T> Internal for T
 where
-    T: Storage<NestingData> + Storage<psp34::Data<enumerable::Balances>>,
+    T: Storage<NestingData> + Storage<psp34::Data<enumerable::Balances>> + psp34::Internal,
 {
     /// Check if child is already accepted
     default fn accepted(
@@ -65,20 +78,66 @@ where
         Ok(())
     }
 
+    /// Check that the child is accepted by the parent token
+    default fn ensure_in_accepted(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        if self
+            .data::<NestingData>()
+            .accepted_children
+            .get(parent_token_id)
+            .unwrap_or_default()
+            .contains(child_nft)
+        {
+            return Ok(())
+        }
+        Err(PSP34Error::Custom(String::from(
+            RmrkError::ChildNotFound.as_str(),
+        )))
+    }
+
+    /// Check that the child is pending on the parent token
+    default fn ensure_in_pending(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        if self
+            .data::<NestingData>()
+            .pending_children
+            .get(parent_token_id)
+            .unwrap_or_default()
+            .contains(child_nft)
+        {
+            return Ok(())
+        }
+        Err(PSP34Error::Custom(String::from(
+            RmrkError::ChildNotFound.as_str(),
+        )))
+    }
+
     /// Add the child to the list of accepted children
-    default fn add_to_accepted(&mut self, parent_token_id: Id, child_nft: ChildNft) {
+    default fn add_to_accepted(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<(), PSP34Error> {
         let mut child_nfts = self
             .data::<NestingData>()
             .accepted_children
             .get(&parent_token_id)
             .unwrap_or(Vec::new());
         if !child_nfts.contains(&child_nft) {
+            self.ensure_children_capacity(&parent_token_id, true, 1)?;
             child_nfts.push(child_nft.clone());
             self.data::<NestingData>()
                 .accepted_children
                 .insert(&parent_token_id, &child_nfts);
             self._emit_child_accepted_event(&parent_token_id, &child_nft.0, &child_nft.1);
         }
+        Ok(())
     }
 
     /// Remove the child to the list of accepted children
@@ -112,18 +171,28 @@ where
     }
 
     /// Add the child to the list of pending children
-    default fn add_to_pending(&mut self, parent_token_id: Id, child_nft: ChildNft) {
+    default fn add_to_pending(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error> {
         let mut child_nfts = self
             .data::<NestingData>()
             .pending_children
             .get(&parent_token_id)
             .unwrap_or(Vec::new());
         if !child_nfts.contains(&child_nft) {
+            self.ensure_children_capacity(&parent_token_id, false, 1)?;
+            self.data::<NestingData>()
+                .child_senders
+                .insert(&child_nft, &sender);
             child_nfts.push(child_nft);
             self.data::<NestingData>()
                 .pending_children
                 .insert(&parent_token_id, &child_nfts);
         }
+        Ok(())
     }
 
     /// Remove the child to the list of pending children
@@ -151,7 +220,180 @@ where
         self.data::<NestingData>()
             .pending_children
             .insert(&parent_token_id, &child_nfts);
+        self.data::<NestingData>().child_senders.remove(child_nft);
+
+        Ok(())
+    }
+
+    /// Check that `amount` more children fit in the accepted or pending list of the parent token
+    default fn ensure_children_capacity(
+        &self,
+        parent_token_id: &Id,
+        accepted: bool,
+        amount: usize,
+    ) -> Result<(), PSP34Error> {
+        let data = self.data::<NestingData>();
+        let (children, max_children, error) = if accepted {
+            (
+                data.accepted_children.get(parent_token_id),
+                data.max_accepted_children,
+                RmrkError::MaxAcceptedChildrenReached,
+            )
+        } else {
+            (
+                data.pending_children.get(parent_token_id),
+                data.max_pending_children,
+                RmrkError::MaxPendingChildrenReached,
+            )
+        };
+        let count = children.map(|children| children.len()).unwrap_or(0);
+        if max_children != 0 && count.saturating_add(amount) > max_children as usize {
+            return Err(PSP34Error::Custom(String::from(error.as_str())))
+        }
+        Ok(())
+    }
+
+    /// Insert the child owned by this contract under the parent token.
+    /// The child goes to the accepted list if `accepted`, to the pending list otherwise
+    default fn nest_child(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        accepted: bool,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error> {
+        self.update_child_parent(&child_nft, Some(parent_token_id.clone()))?;
+        self._emit_added_child_event(&parent_token_id, &child_nft.0, &child_nft.1);
+        if accepted {
+            self.add_to_accepted(parent_token_id, child_nft)
+        } else {
+            self.add_to_pending(parent_token_id, child_nft, sender)
+        }
+    }
+
+    /// Check that the child can be added to the parent token and that the caller may hand it over.
+    /// Returns the current owner of the child
+    default fn ensure_addable_child(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<AccountId, PSP34Error> {
+        self.ensure_allowed_child_collection(&child_nft.0)?;
+        self.ensure_valid_nesting(parent_token_id, child_nft)?;
+        self.accepted(parent_token_id, child_nft)?;
+        self.pending(parent_token_id, child_nft)?;
+
+        let this = Self::env().account_id();
+        if child_nft.0 == this {
+            // A child nested in this collection is owned by this contract and must be
+            // moved with transfer_child()
+            let caller = Self::env().caller();
+            let token_owner = self.ensure_exists(&child_nft.1)?;
+            if token_owner != caller && !self._allowance(&token_owner, &caller, &Some(&child_nft.1))
+            {
+                return Err(PSP34Error::NotApproved)
+            }
+            return Ok(token_owner)
+        }
+
+        let token_owner = PSP34Ref::owner_of_builder(&child_nft.0, child_nft.1.clone())
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+            .map_err(child_contract_error)?
+            .ok_or(PSP34Error::TokenNotExists)?;
+        // The child collection only lets this contract take the child if it is approved
+        let approved = token_owner != this
+            && PSP34Ref::allowance_builder(
+                &child_nft.0,
+                token_owner,
+                this,
+                Some(child_nft.1.clone()),
+            )
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+            .map_err(child_contract_error)?;
+        if !approved {
+            return Err(PSP34Error::NotApproved)
+        }
+        Ok(token_owner)
+    }
+
+    /// Transfer the child to this contract and insert it under the parent token.
+    /// The child is given back to `sender` if it can't be inserted
+    default fn insert_child(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        accepted: bool,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error> {
+        self.move_child(Self::env().account_id(), child_nft.clone())?;
 
+        if let Err(error) = self.nest_child(parent_token_id, child_nft.clone(), accepted, sender) {
+            // Nothing is recorded for the child, give it back
+            self.move_child(sender, child_nft)?;
+            return Err(error)
+        }
+        Ok(())
+    }
+
+    /// Return the pending child to the account which sent it
+    default fn return_pending_child(
+        &mut self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        let sender = match self.data::<NestingData>().child_senders.get(child_nft) {
+            Some(sender) => sender,
+            None => self.get_root_owner(parent_token_id)?,
+        };
+        self.release_child(parent_token_id, child_nft, sender)?;
+        self.remove_from_pending(parent_token_id, child_nft)?;
+        self._emit_child_rejected_event(parent_token_id, &child_nft.0, &child_nft.1);
+        Ok(())
+    }
+
+    /// Return the pending children of the parent token to the accounts which sent them
+    default fn return_pending_children(
+        &mut self,
+        parent_token_id: &Id,
+        max_rejections: u32,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_exists(parent_token_id)?;
+        let pending_children = self
+            .data::<NestingData>()
+            .pending_children
+            .get(parent_token_id)
+            .unwrap_or_default();
+        if pending_children.len() > max_rejections as usize {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::MaxRejectionsReached.as_str(),
+            )))
+        }
+
+        // Each child leaves the list once it is back with its sender
+        for child_nft in pending_children {
+            self.return_pending_child(parent_token_id, &child_nft)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that a batch call does not exceed `MAX_BATCH_CHILDREN` children
+    /// and names each child only once
+    default fn ensure_valid_batch(&self, children: &[ChildNft]) -> Result<(), PSP34Error> {
+        if children.len() > MAX_BATCH_CHILDREN as usize {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::MaxBatchChildrenExceeded.as_str(),
+            )))
+        }
+        for (index, child_nft) in children.iter().enumerate() {
+            if children[..index].contains(child_nft) {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::AlreadyAddedChild.as_str(),
+                )))
+            }
+        }
         Ok(())
     }
 
@@ -164,7 +406,202 @@ where
         Ok(token_owner)
     }
 
-    /// Check if caller is the owner of this parent token
+    /// Get the direct owner of the token and the parent tokenId if the token is nested
+    default fn get_direct_owner(
+        &self,
+        token_id: &Id,
+    ) -> Result<(AccountId, Option<Id>), PSP34Error> {
+        let token_owner = self.ensure_exists(token_id)?;
+        let parent_token_id = match self.data::<NestingData>().parents.get(token_id) {
+            // The record is outdated once the token has left the parent collection
+            Some((collection, parent_token_id)) if collection == token_owner => {
+                Some(parent_token_id)
+            }
+            _ => None,
+        };
+        Ok((token_owner, parent_token_id))
+    }
+
+    /// Get the owner of the top most parent token
+    default fn get_root_owner(&self, token_id: &Id) -> Result<AccountId, PSP34Error> {
+        let this = Self::env().account_id();
+        let mut token_id = token_id.clone();
+        loop {
+            match self.get_direct_owner(&token_id)? {
+                (token_owner, None) => return Ok(token_owner),
+                (token_owner, Some(parent_token_id)) if token_owner == this => {
+                    token_id = parent_token_id
+                }
+                (token_owner, Some(parent_token_id)) => {
+                    return NestingRef::root_owner_of_builder(&token_owner, parent_token_id)
+                        .call_flags(CallFlags::default().set_allow_reentry(true))
+                        .fire()
+                        .map_err(child_contract_error)?
+                }
+            }
+        }
+    }
+
+    /// Record the parent token in the child collection
+    default fn update_child_parent(
+        &mut self,
+        child_nft: &ChildNft,
+        parent_token_id: Option<Id>,
+    ) -> Result<(), PSP34Error> {
+        let this = Self::env().account_id();
+        if child_nft.0 != this {
+            return self.set_child_parent(child_nft, parent_token_id)
+        }
+        match parent_token_id {
+            Some(parent_token_id) => {
+                self.data::<NestingData>()
+                    .parents
+                    .insert(&child_nft.1, &(this, parent_token_id))
+            }
+            None => self.data::<NestingData>().parents.remove(&child_nft.1),
+        }
+        Ok(())
+    }
+
+    /// Hand the child over to `to` and clear its parent record.
+    /// The parent record is restored if the transfer fails
+    default fn release_child(
+        &mut self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+        to: AccountId,
+    ) -> Result<(), PSP34Error> {
+        self.update_child_parent(child_nft, None)?;
+        if let Err(error) = self.move_child(to, child_nft.clone()) {
+            self.update_child_parent(child_nft, Some(parent_token_id.clone()))?;
+            return Err(error)
+        }
+        Ok(())
+    }
+
+    /// Transfer the child to `to`, within this collection or through a cross contract call
+    default fn move_child(&mut self, to: AccountId, child_nft: ChildNft) -> Result<(), PSP34Error> {
+        let this = Self::env().account_id();
+        if child_nft.0 != this {
+            return self.transfer_child_ownership(to, child_nft)
+        }
+        if self.ensure_exists(&child_nft.1)? != this {
+            // Caller must be the owner of the child or an operator approved by it
+            return self._transfer_token(to, child_nft.1, Vec::new())
+        }
+
+        // The token is held by this contract, which can't be the caller of its own transfer
+        let token_id = child_nft.1;
+        self._before_token_transfer(Some(&this), Some(&to), &token_id)?;
+        let data = self.data::<psp34::Data<enumerable::Balances>>();
+        data.balances.decrease_balance(&this, &token_id, false);
+        data.token_owner.insert(&token_id, &to);
+        data.balances.increase_balance(&to, &token_id, false);
+        self._after_token_transfer(Some(&this), Some(&to), &token_id)?;
+        self._emit_transfer_event(Some(this), Some(to), token_id);
+        Ok(())
+    }
+
+    /// Walk up from the parent token to check that the child is not one of its ancestors
+    /// and that the child does not exceed the maximum nesting depth
+    default fn ensure_valid_nesting(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        let this = Self::env().account_id();
+        let max_nesting_depth = self.data::<NestingData>().max_nesting_depth;
+        let mut current: ChildNft = (this, parent_token_id.clone());
+        // The children of the child move down together with it
+        let mut depth: u32 = 1;
+        if max_nesting_depth != 0 {
+            depth += self.get_children_depth(child_nft)?;
+        }
+        loop {
+            if current == *child_nft {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::NestingCycleDetected.as_str(),
+                )))
+            }
+            if max_nesting_depth != 0 && depth > max_nesting_depth {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::MaxNestingDepthExceeded.as_str(),
+                )))
+            }
+
+            let (token_owner, parent_token_id) = if current.0 == this {
+                self.get_direct_owner(&current.1)?
+            } else {
+                NestingRef::direct_owner_of_builder(&current.0, current.1.clone())
+                    .call_flags(CallFlags::default().set_allow_reentry(true))
+                    .fire()
+                    .map_err(child_contract_error)??
+            };
+            match parent_token_id {
+                Some(parent_token_id) => current = (token_owner, parent_token_id),
+                None => return Ok(()),
+            }
+            depth += 1;
+        }
+    }
+
+    /// Get the number of levels of accepted and pending children below the token
+    default fn get_children_depth(&self, token: &ChildNft) -> Result<u32, PSP34Error> {
+        if token.0 != Self::env().account_id() {
+            return match NestingRef::children_depth_builder(&token.0, token.1.clone())
+                .call_flags(CallFlags::default().set_allow_reentry(true))
+                .fire()
+            {
+                Ok(depth) => depth,
+                // Collections which are PSP34 only don't know the message, so the call traps
+                Err(ink_env::Error::CalleeTrapped) => Ok(0),
+                Err(error) => Err(child_contract_error(error)),
+            }
+        }
+
+        let mut children = self
+            .data::<NestingData>()
+            .accepted_children
+            .get(&token.1)
+            .unwrap_or_default();
+        children.append(
+            &mut self
+                .data::<NestingData>()
+                .pending_children
+                .get(&token.1)
+                .unwrap_or_default(),
+        );
+        let mut depth = 0;
+        for child_nft in children.iter() {
+            depth = depth.max(self.get_children_depth(child_nft)? + 1);
+        }
+        Ok(depth)
+    }
+
+    /// Check if the child collection is approved by this (parent) collection
+    default fn ensure_allowed_child_collection(
+        &self,
+        collection: &CollectionId,
+    ) -> Result<(), PSP34Error> {
+        let data = self.data::<NestingData>();
+        if *collection != Self::env().account_id()
+            && !data.allow_all_child_collections
+            && !data
+                .allowed_child_collections
+                .get(collection)
+                .unwrap_or(false)
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::ChildContractNotApproved.as_str(),
+            )))
+        }
+
+        Ok(())
+    }
+
+    /// Check if caller is the root owner of this parent token or an operator approved by it.
+    /// A nested parent is held by its parent collection and can't be approved on its own,
+    /// so only operators approved by the root owner for all tokens qualify
     default fn is_caller_parent_owner(
         &self,
         caller: AccountId,
@@ -174,7 +611,13 @@ where
             .data::<psp34::Data<enumerable::Balances>>()
             .owner_of(parent_token_id.clone())
         {
-            if token_owner != caller {
+            if token_owner == caller {
+                return Ok(())
+            }
+            let root_owner = self.get_root_owner(parent_token_id)?;
+            if root_owner != caller
+                && !self._allowance(&root_owner, &caller, &Some(parent_token_id))
+            {
                 return Err(PSP34Error::Custom(String::from(
                     RmrkError::NotAuthorised.as_str(),
                 )))
@@ -183,29 +626,167 @@ where
         Ok(())
     }
 
+    /// Hook called before an accepted child leaves the parent token
+    default fn _before_child_removed(
+        &self,
+        _parent_token_id: &Id,
+        _child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error> {
+        Ok(())
+    }
+
+    /// Burn the token, its accepted children (recursively) and release the pending children
+    default fn burn_recursive(
+        &mut self,
+        token_id: &Id,
+        max_recursive_burns: u32,
+    ) -> Result<u32, PSP34Error> {
+        let token_owner = self.ensure_exists(token_id)?;
+        let root_owner = self.get_root_owner(token_id)?;
+        let this = Self::env().account_id();
+
+        let mut total_child_burns: u32 = 0;
+        let accepted_children = self
+            .data::<NestingData>()
+            .accepted_children
+            .get(token_id)
+            .unwrap_or_default();
+        for child_nft in accepted_children {
+            self._before_child_removed(token_id, &child_nft)?;
+            let pending_recursive_burns = max_recursive_burns.saturating_sub(total_child_burns);
+            if pending_recursive_burns == 0 {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::MaxRecursiveBurnsReached.as_str(),
+                )))
+            }
+
+            if child_nft.0 == this {
+                total_child_burns +=
+                    self.burn_recursive(&child_nft.1, pending_recursive_burns - 1)? + 1;
+            } else {
+                match NestingRef::burn_builder(
+                    &child_nft.0,
+                    child_nft.1.clone(),
+                    pending_recursive_burns - 1,
+                )
+                .call_flags(CallFlags::default().set_allow_reentry(true))
+                .fire()
+                {
+                    Ok(child_burns) => total_child_burns += child_burns? + 1,
+                    // Child collection is not a RMRK collection, so the child can't be burned
+                    Err(_) => self.release_child(token_id, &child_nft, root_owner)?,
+                }
+            }
+            self._emit_child_removed_event(token_id, &child_nft.0, &child_nft.1);
+        }
+        self.data::<NestingData>()
+            .accepted_children
+            .remove(token_id);
+
+        self.return_pending_children(token_id, u32::MAX)?;
+        self.data::<NestingData>().parents.remove(token_id);
+
+        self._burn_from(token_owner, token_id.clone())?;
+
+        Ok(total_child_burns)
+    }
+
     /// Cross contract call to transfer child nft ownership
     default fn transfer_child_ownership(
         &self,
         to: AccountId,
         child_nft: ChildNft,
     ) -> Result<(), PSP34Error> {
-        // TODO check child collection is approved by this (parent) collection
-        // let collection = self.get_collection(child_nft.0)
-        //      .ok_or(RmrkError::ChildContractNotApproved)?;
-
         PSP34Ref::transfer_builder(&child_nft.0, to, child_nft.1, Vec::new())
             .call_flags(CallFlags::default().set_allow_reentry(true))
             .fire()
-            .unwrap()?;
-        ink_env::debug_println!("####### transfer  executed!!!!");
+            .map_err(child_contract_error)?
+    }
 
+    /// Cross contract call to record the parent token in the child collection
+    default fn set_child_parent(
+        &self,
+        child_nft: &ChildNft,
+        parent_token_id: Option<Id>,
+    ) -> Result<(), PSP34Error> {
+        match NestingRef::set_parent_builder(&child_nft.0, child_nft.1.clone(), parent_token_id)
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+        {
+            Ok(result) => result,
+            // Collections which are PSP34 only don't know the message, so the call traps
+            Err(ink_env::Error::CalleeTrapped) => Ok(()),
+            Err(error) => Err(child_contract_error(error)),
+        }
+    }
+
+    /// Cross contract calls to check that the parent token in another collection exists
+    /// and takes `amount` more pending children from this collection
+    default fn ensure_remote_parent(
+        &self,
+        collection: AccountId,
+        parent_token_id: &Id,
+        amount: u64,
+    ) -> Result<(), PSP34Error> {
+        let (_, pending_children) =
+            NestingRef::children_balance_builder(&collection, parent_token_id.clone())
+                .call_flags(CallFlags::default().set_allow_reentry(true))
+                .fire()
+                .map_err(child_contract_error)??;
+        let allowed = NestingRef::is_allowed_builder(&collection, Self::env().account_id())
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+            .map_err(child_contract_error)?;
+        if !allowed {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::ChildContractNotApproved.as_str(),
+            )))
+        }
+
+        // Children sent by another contract always wait in the pending list
+        let (_, max_pending_children) = NestingRef::children_limits_builder(&collection)
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+            .map_err(child_contract_error)?;
+        if max_pending_children != 0
+            && pending_children.saturating_add(amount) > max_pending_children as u64
+        {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::MaxPendingChildrenReached.as_str(),
+            )))
+        }
         Ok(())
     }
+
+    /// Approve the parent collection for the token and let it add the token as a child.
+    /// The approval is revoked if the parent collection refuses the token
+    default fn nest_into_collection(
+        &mut self,
+        collection: AccountId,
+        parent_token_id: Id,
+        token_id: Id,
+    ) -> Result<(), PSP34Error> {
+        // The parent contract pulls the token within add_child()
+        self._approve_for(collection, Some(token_id.clone()), true)?;
+        let child_nft = (Self::env().account_id(), token_id.clone());
+        let result = NestingRef::add_child_builder(&collection, parent_token_id, child_nft)
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire()
+            .map_err(child_contract_error)
+            .and_then(|result| result);
+        if result.is_err() {
+            self._approve_for(collection, Some(token_id), false)?;
+        }
+        result
+    }
 }
 
 impl<T> Nesting for T
 where
-    T: Storage<NestingData> + Storage<psp34::Data<enumerable::Balances>>,
+    T: Storage<NestingData>
+        + Storage<psp34::Data<enumerable::Balances>>
+        + Storage<ownable::Data>
+        + psp34::Internal,
 {
     /// Add a child NFT (from different collection) to the NFT in this collection
     /// The status of the added child is `Pending` if caller is not owner of child NFT
@@ -219,6 +800,8 @@ where
     /// * `to_parent_token_id` must exist.
     /// * `child_token_id` must exist.
     /// * There cannot be two identical children.
+    /// * Caller is the owner of a child from this collection or an operator approved by it.
+    /// * A child from another collection has approved this contract.
     ///
     /// # Arguments:
     /// * `to_parent_token_id`: is the tokenId of the parent NFT. The receiver of child.
@@ -234,30 +817,97 @@ where
         child_nft: ChildNft,
     ) -> Result<(), PSP34Error> {
         let parent_owner = self.ensure_exists(&to_parent_token_id)?;
-        self.accepted(&to_parent_token_id, &child_nft)?;
-        self.pending(&to_parent_token_id, &child_nft)?;
+        // The child is returned to its current owner if it gets rejected
+        let sender = self.ensure_addable_child(&to_parent_token_id, &child_nft)?;
+        let accepted = Self::env().caller() == parent_owner;
+        self.ensure_children_capacity(&to_parent_token_id, accepted, 1)?;
 
-        // Transfer child ownership to this contract.
-        // This transfer call will fail if caller is not child owner
-        self.transfer_child_ownership(Self::env().account_id(), child_nft.clone())?;
+        // Transfer child ownership to this contract and insert child nft
+        self.insert_child(to_parent_token_id, child_nft, accepted, sender)
+    }
 
-        // Insert child nft and emit event
-        self._emit_added_child_event(&to_parent_token_id, &child_nft.0, &child_nft.1);
-        let caller = Self::env().caller();
-        if caller == parent_owner {
-            self.add_to_accepted(to_parent_token_id, child_nft);
-        } else {
-            self.add_to_pending(to_parent_token_id, child_nft);
+    /// Add several child NFTs to the NFT in this collection, as `add_child()` does for each
+    ///
+    /// # Requirements:
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * Each child meets the requirements of `add_child()`
+    /// * The whole batch is checked before the first child is moved
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT. The receiver of children.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Ownership of the children will be transferred to this contract (cross contract call)
+    /// On success emitts `RmrkEvent::ChildAdded` for each child
+    /// On success emitts `RmrkEvent::ChildAccepted` for each child - only if caller is parent owner
+    default fn add_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_valid_batch(&children)?;
+        let parent_owner = self.ensure_exists(&parent_token_id)?;
+        let accepted = Self::env().caller() == parent_owner;
+        self.ensure_children_capacity(&parent_token_id, accepted, children.len())?;
+        // Check the whole batch before the first child is moved
+        let mut senders = Vec::with_capacity(children.len());
+        for child_nft in children.iter() {
+            senders.push(self.ensure_addable_child(&parent_token_id, child_nft)?);
+        }
+
+        for (child_nft, sender) in children.into_iter().zip(senders) {
+            self.insert_child(parent_token_id.clone(), child_nft, accepted, sender)?;
         }
 
         Ok(())
     }
 
+    /// Nest a token of this collection into a parent token, in this or another RMRK collection
+    ///
+    /// # Requirements:
+    /// * Caller is the owner of the token or an operator approved by it
+    /// * `to_parent_id` must exist in `to_collection`
+    /// * `to_collection` allows children of this collection and the parent has room for one more
+    ///
+    /// # Arguments:
+    /// * `to_collection`: address of the parent contract
+    /// * `to_parent_id`: tokenId of the parent NFT. The receiver of the token.
+    /// * `token_id`: tokenId of the token to nest
+    ///
+    /// # Result:
+    /// Ownership of the token is transferred to the parent contract (cross contract call)
+    /// On success emitts `RmrkEvent::ChildAdded` in the parent contract
+    /// On failure the token stays with its owner and the approval of the parent contract is revoked
+    default fn nest_transfer(
+        &mut self,
+        to_collection: AccountId,
+        to_parent_id: Id,
+        token_id: Id,
+    ) -> Result<(), PSP34Error> {
+        let this = Self::env().account_id();
+        let token_owner = self.ensure_exists(&token_id)?;
+        let child_nft = (this, token_id.clone());
+
+        if to_collection == this {
+            let parent_owner = self.ensure_exists(&to_parent_id)?;
+            self.ensure_valid_nesting(&to_parent_id, &child_nft)?;
+            let accepted = Self::env().caller() == parent_owner;
+            self.ensure_children_capacity(&to_parent_id, accepted, 1)?;
+            self._transfer_token(this, token_id, Vec::new())?;
+            self.nest_child(to_parent_id, child_nft, accepted, token_owner)
+        } else {
+            self.ensure_remote_parent(to_collection, &to_parent_id, 1)?;
+            self.nest_into_collection(to_collection, to_parent_id, token_id)
+        }
+    }
+
     /// Remove a child NFT (from different collection) from token_id in this collection
     /// The status of added child is `Pending` if caller is not owner of child NFT
     /// The status of added child is `Accepted` if caller is is owner of child NFT
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Accepted`
     ///
     /// # Arguments:
@@ -265,7 +915,7 @@ where
     /// * `child_nft`: (collection_id, token_id) of the child instance.
     ///
     /// # Result:
-    /// Ownership of child NFT will be transferred to parent NFT owner (cross contract call)
+    /// Ownership of child NFT will be transferred to the root owner of parent NFT (cross contract call)
     /// On success emitts `RmrkEvent::ChildRemoved`
     default fn remove_child(
         &mut self,
@@ -275,21 +925,22 @@ where
         self.ensure_exists(&parent_token_id)?;
         let caller = Self::env().caller();
         self.is_caller_parent_owner(caller, &parent_token_id)?;
+        self._before_child_removed(&parent_token_id, &child_nft)?;
+        self.ensure_in_accepted(&parent_token_id, &child_nft)?;
 
-        // Remove child nft
-        self.remove_accepted(&parent_token_id, &child_nft)?;
-
-        // Transfer child ownership from this contract to parent_token owner.
-        // This call will fail if this contract is not child owner
-        let token_owner = self.ensure_exists(&parent_token_id)?;
-        self.transfer_child_ownership(token_owner, child_nft)?;
+        // Transfer child ownership from this contract to the root owner of parent_token.
+        // A nested parent is held by this contract, so its direct owner can't take the child
+        let root_owner = self.get_root_owner(&parent_token_id)?;
+        self.release_child(&parent_token_id, &child_nft, root_owner)?;
 
-        Ok(())
+        // Remove child nft
+        self.remove_accepted(&parent_token_id, &child_nft)
     }
 
     /// Accept a child NFT (from different collection) to be owned by parent token
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Pending`
     ///
     /// # Arguments:
@@ -308,9 +959,49 @@ where
         let caller = Self::env().caller();
         self.is_caller_parent_owner(caller, &parent_token_id)?;
         self.accepted(&parent_token_id, &child_nft)?;
+        self.ensure_in_pending(&parent_token_id, &child_nft)?;
+        self.ensure_children_capacity(&parent_token_id, true, 1)?;
 
         self.remove_from_pending(&parent_token_id, &child_nft)?;
-        self.add_to_accepted(parent_token_id, child_nft);
+        self.add_to_accepted(parent_token_id, child_nft)?;
+
+        Ok(())
+    }
+
+    /// Accept several pending children of the parent token
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * The status of each child is `Pending`
+    /// * The whole batch is checked before the first child is changed
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Children are moved from pending to accepted
+    /// On success emitts `RmrkEvent::ChildAccepted` for each child
+    default fn accept_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_valid_batch(&children)?;
+        self.ensure_exists(&parent_token_id)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &parent_token_id)?;
+        for child_nft in children.iter() {
+            self.accepted(&parent_token_id, child_nft)?;
+            self.ensure_in_pending(&parent_token_id, child_nft)?;
+        }
+        self.ensure_children_capacity(&parent_token_id, true, children.len())?;
+
+        for child_nft in children {
+            self.remove_from_pending(&parent_token_id, &child_nft)?;
+            self.add_to_accepted(parent_token_id.clone(), child_nft)?;
+        }
 
         Ok(())
     }
@@ -318,6 +1009,7 @@ where
     /// Reject a child NFT (from different collection)
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Pending`
     ///
     /// # Arguments:
@@ -325,7 +1017,7 @@ where
     /// * `child_nft`: (collection_id, token_id) of the child instance.
     ///
     /// # Result:
-    /// Child Nft is removed from pending
+    /// Child Nft is removed from pending and returned to the account which sent it (cross contract call)
     /// On success emitts `RmrkEvent::ChildRejected`
     default fn reject_child(
         &mut self,
@@ -336,16 +1028,76 @@ where
         let caller = Self::env().caller();
         self.is_caller_parent_owner(caller, &parent_token_id)?;
         self.accepted(&parent_token_id, &child_nft)?;
+        self.ensure_in_pending(&parent_token_id, &child_nft)?;
 
-        self.remove_from_pending(&parent_token_id, &child_nft)?;
-        self._emit_child_rejected_event(&parent_token_id, &child_nft.0, &child_nft.1);
+        self.return_pending_child(&parent_token_id, &child_nft)
+    }
+
+    /// Reject several pending children of the parent token
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * The status of each child is `Pending`
+    /// * The whole batch is checked before the first child is changed
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Children are removed from pending and returned to the accounts which sent them (cross contract call)
+    /// On success emitts `RmrkEvent::ChildRejected` for each child
+    default fn reject_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_valid_batch(&children)?;
+        self.ensure_exists(&parent_token_id)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &parent_token_id)?;
+        for child_nft in children.iter() {
+            self.accepted(&parent_token_id, child_nft)?;
+            self.ensure_in_pending(&parent_token_id, child_nft)?;
+        }
+
+        for child_nft in children {
+            self.return_pending_child(&parent_token_id, &child_nft)?;
+        }
 
         Ok(())
     }
 
+    /// Reject all pending children of the parent token
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * The number of pending children is not above `max_rejections`
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `max_rejections`: maximum number of children expected to be rejected
+    ///
+    /// # Result:
+    /// Ownership of the children is returned to the accounts which sent them (cross contract call)
+    /// On success emitts `RmrkEvent::ChildRejected` for each child
+    default fn reject_all_children(
+        &mut self,
+        parent_token_id: Id,
+        max_rejections: u32,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_exists(&parent_token_id)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &parent_token_id)?;
+
+        self.return_pending_children(&parent_token_id, max_rejections)
+    }
+
     /// Transfer the child NFT from one parent to another (in this collection)
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the current parent token or an operator approved by it
     /// * The status of the child is `Accepted`
     ///
     /// # Arguments:
@@ -356,22 +1108,34 @@ where
     /// # Result:
     /// Ownership of child NFT will be transferred to this contract (cross contract call)
     /// On success emitts `RmrkEvent::ChildAdded`
-    /// On success emitts `RmrkEvent::ChildAccepted` - only if caller is already owner of child NFT
+    /// On success emitts `RmrkEvent::ChildAccepted` - only if both parents have the same root owner
     default fn transfer_child(
         &mut self,
         current_parent: Id,
         new_parent: Id,
         child_nft: ChildNft,
     ) -> Result<(), PSP34Error> {
-        let current_parent_owner = self.ensure_exists(&current_parent)?;
-        let new_parent_owner = self.ensure_exists(&new_parent)?;
+        self.ensure_exists(&current_parent)?;
+        self.ensure_exists(&new_parent)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &current_parent)?;
+        self.ensure_valid_nesting(&new_parent, &child_nft)?;
+        self._before_child_removed(&current_parent, &child_nft)?;
+        self.ensure_in_accepted(&current_parent, &child_nft)?;
+        self.accepted(&new_parent, &child_nft)?;
+        self.pending(&new_parent, &child_nft)?;
+        // The child stays with the same holder when both parents share the root owner
+        let sender = self.get_root_owner(&current_parent)?;
+        let accepted = sender == self.get_root_owner(&new_parent)?;
+        self.ensure_children_capacity(&new_parent, accepted, 1)?;
+        self.update_child_parent(&child_nft, Some(new_parent.clone()))?;
         self.remove_accepted(&current_parent, &child_nft)?;
 
         self._emit_added_child_event(&new_parent, &child_nft.0, &child_nft.1);
-        if current_parent_owner == new_parent_owner {
-            self.add_to_accepted(new_parent, child_nft);
+        if accepted {
+            self.add_to_accepted(new_parent, child_nft)?;
         } else {
-            self.add_to_pending(new_parent, child_nft);
+            self.add_to_pending(new_parent, child_nft, sender)?;
         }
 
         Ok(())
@@ -408,6 +1172,369 @@ where
             parents_with_pending_children,
         ))
     }
+
+    /// Reorder the accepted children of the parent token
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * `children` contains exactly the accepted children of the parent token
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: accepted children of the parent in the new order
+    ///
+    /// # Result:
+    /// On success emitts `RmrkEvent::ChildrenOrderSet`
+    default fn set_children_order(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error> {
+        self.ensure_exists(&parent_token_id)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &parent_token_id)?;
+
+        let accepted_children = self
+            .data::<NestingData>()
+            .accepted_children
+            .get(&parent_token_id)
+            .unwrap_or_default();
+        if accepted_children.len() != children.len() {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::BadPriorityLength.as_str(),
+            )))
+        }
+        for child_nft in accepted_children.iter() {
+            if !children.contains(child_nft) {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::ChildNotFound.as_str(),
+                )))
+            }
+        }
+
+        self.data::<NestingData>()
+            .accepted_children
+            .insert(&parent_token_id, &children);
+        self._emit_children_order_set_event(&parent_token_id, children);
+        Ok(())
+    }
+
+    /// Read a page of the accepted children of the parent token
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `offset`: index of the first child to return
+    /// * `limit`: maximum number of children to return
+    ///
+    /// # Result:
+    /// Returns the (collection_id, token_id) of the accepted children
+    default fn get_accepted_children(
+        &self,
+        parent_token_id: Id,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ChildNft>, PSP34Error> {
+        self.ensure_exists(&parent_token_id)?;
+        Ok(self
+            .data::<NestingData>()
+            .accepted_children
+            .get(&parent_token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Read a page of the pending children of the parent token
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `offset`: index of the first child to return
+    /// * `limit`: maximum number of children to return
+    ///
+    /// # Result:
+    /// Returns the (collection_id, token_id) of the pending children
+    default fn get_pending_children(
+        &self,
+        parent_token_id: Id,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ChildNft>, PSP34Error> {
+        self.ensure_exists(&parent_token_id)?;
+        Ok(self
+            .data::<NestingData>()
+            .pending_children
+            .get(&parent_token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Read the position of the child among the accepted children of the parent token
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `child_nft`: (collection_id, token_id) of the child instance.
+    ///
+    /// # Result:
+    /// Returns the index of the child, `None` if the child is not accepted by the parent
+    default fn child_index(
+        &self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<Option<u32>, PSP34Error> {
+        self.ensure_exists(&parent_token_id)?;
+        Ok(self
+            .data::<NestingData>()
+            .accepted_children
+            .get(&parent_token_id)
+            .unwrap_or_default()
+            .iter()
+            .position(|child| *child == child_nft)
+            .map(|index| index as u32))
+    }
+
+    /// Burn the token together with its accepted children
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the token or an operator approved by it
+    /// * Accepted children are not equipped
+    /// * A nested token is accepted by its parent and not equipped. It is removed from the parent.
+    /// * A token nested into another collection is burned only by that collection
+    ///
+    /// # Arguments:
+    /// * `token_id`: tokenId of the token to burn
+    /// * `max_recursive_burns`: maximum number of children which can be burned, at any depth
+    ///
+    /// # Result:
+    /// Children from RMRK collections are burned recursively (cross contract call).
+    /// Children which can't be burned are transferred to the root owner of the token.
+    /// Pending children are returned to the accounts which sent them.
+    /// Returns the number of burned children.
+    /// On success emitts `RmrkEvent::ChildRemoved` for each accepted child
+    default fn burn(&mut self, token_id: Id, max_recursive_burns: u32) -> Result<u32, PSP34Error> {
+        self.ensure_exists(&token_id)?;
+        let caller = Self::env().caller();
+        self.is_caller_parent_owner(caller, &token_id)?;
+
+        let this = Self::env().account_id();
+        let child_nft = (this, token_id.clone());
+        let parent_token_id = match self.get_direct_owner(&token_id)? {
+            (token_owner, Some(parent_token_id)) if token_owner == this => {
+                self.ensure_in_accepted(&parent_token_id, &child_nft)?;
+                self._before_child_removed(&parent_token_id, &child_nft)?;
+                Some(parent_token_id)
+            }
+            // The parent collection has to remove the token from its children first
+            (token_owner, Some(_)) if token_owner != caller => {
+                return Err(PSP34Error::Custom(String::from(
+                    RmrkError::NotAuthorised.as_str(),
+                )))
+            }
+            _ => None,
+        };
+
+        let total_child_burns = self.burn_recursive(&token_id, max_recursive_burns)?;
+        // The parent must not keep the burned token among its children
+        if let Some(parent_token_id) = parent_token_id {
+            self.remove_accepted(&parent_token_id, &child_nft)?;
+        }
+        Ok(total_child_burns)
+    }
+
+    /// Read the number of levels of children below the token
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns 0 if the token has no children
+    default fn children_depth(&self, token_id: Id) -> Result<u32, PSP34Error> {
+        self.ensure_exists(&token_id)?;
+        self.get_children_depth(&(Self::env().account_id(), token_id))
+    }
+
+    /// Read the direct owner of the token
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the owner account and, if the token is nested, the tokenId of the parent
+    /// in the owner collection
+    default fn direct_owner_of(&self, token_id: Id) -> Result<(AccountId, Option<Id>), PSP34Error> {
+        self.get_direct_owner(&token_id)
+    }
+
+    /// Read the root owner of the token, walking up the parent tokens (cross contract call)
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the owner of the top most parent token
+    default fn root_owner_of(&self, token_id: Id) -> Result<AccountId, PSP34Error> {
+        self.get_root_owner(&token_id)
+    }
+
+    /// Read the parent of the token
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the parent (collection_id, token_id) if the token is nested, `None` otherwise
+    default fn parent_of(&self, token_id: Id) -> Option<(CollectionId, Id)> {
+        let token_owner = self
+            .data::<psp34::Data<enumerable::Balances>>()
+            .owner_of(token_id.clone())?;
+        self.data::<NestingData>()
+            .parents
+            .get(&token_id)
+            .filter(|(collection, _)| *collection == token_owner)
+    }
+
+    /// Record the parent of the token once it is nested into the caller collection
+    ///
+    /// # Requirements:
+    /// * Caller is the owner of the token, i.e. the parent collection
+    ///
+    /// # Arguments:
+    /// * `token_id`: tokenId of the nested token
+    /// * `parent_token_id`: tokenId of the parent in the caller collection, `None` to clear it
+    default fn set_parent(
+        &mut self,
+        token_id: Id,
+        parent_token_id: Option<Id>,
+    ) -> Result<(), PSP34Error> {
+        let token_owner = self.ensure_exists(&token_id)?;
+        let caller = Self::env().caller();
+        if caller != token_owner {
+            return Err(PSP34Error::Custom(String::from(
+                RmrkError::NotAuthorised.as_str(),
+            )))
+        }
+        match parent_token_id {
+            Some(parent_token_id) => {
+                self.data::<NestingData>()
+                    .parents
+                    .insert(&token_id, &(caller, parent_token_id))
+            }
+            None => self.data::<NestingData>().parents.remove(&token_id),
+        }
+
+        Ok(())
+    }
+
+    /// Approve a child collection to be nested into the tokens of this collection
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    #[modifiers(only_owner)]
+    default fn add_allowed_child_collection(
+        &mut self,
+        collection: CollectionId,
+    ) -> Result<(), PSP34Error> {
+        self.data::<NestingData>()
+            .allowed_child_collections
+            .insert(&collection, &true);
+
+        Ok(())
+    }
+
+    /// Revoke the approval of a child collection. Already nested children are not affected
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    #[modifiers(only_owner)]
+    default fn remove_allowed_child_collection(
+        &mut self,
+        collection: CollectionId,
+    ) -> Result<(), PSP34Error> {
+        self.data::<NestingData>()
+            .allowed_child_collections
+            .remove(&collection);
+
+        Ok(())
+    }
+
+    /// Accept children from any collection, regardless of the approved ones
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `allow_all`: `true` to accept any collection, `false` to accept only approved ones
+    #[modifiers(only_owner)]
+    default fn set_allow_all_child_collections(
+        &mut self,
+        allow_all: bool,
+    ) -> Result<(), PSP34Error> {
+        self.data::<NestingData>().allow_all_child_collections = allow_all;
+
+        Ok(())
+    }
+
+    /// Set the maximum number of parents above a nested token
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `max_nesting_depth`: maximum depth, 0 for no limit
+    #[modifiers(only_owner)]
+    default fn set_max_nesting_depth(&mut self, max_nesting_depth: u32) -> Result<(), PSP34Error> {
+        self.data::<NestingData>().max_nesting_depth = max_nesting_depth;
+
+        Ok(())
+    }
+
+    /// Read the maximum number of parents above a nested token, 0 for no limit
+    default fn max_nesting_depth(&self) -> u32 {
+        self.data::<NestingData>().max_nesting_depth
+    }
+
+    /// Set the maximum number of accepted and pending children per parent token
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `max_accepted_children`: maximum number of accepted children, 0 for no limit
+    /// * `max_pending_children`: maximum number of pending children, 0 for no limit
+    #[modifiers(only_owner)]
+    default fn set_children_limits(
+        &mut self,
+        max_accepted_children: u32,
+        max_pending_children: u32,
+    ) -> Result<(), PSP34Error> {
+        let data = self.data::<NestingData>();
+        data.max_accepted_children = max_accepted_children;
+        data.max_pending_children = max_pending_children;
+
+        Ok(())
+    }
+
+    /// Read the maximum number of children per parent token, 0 for no limit
+    ///
+    /// # Result:
+    /// Returns the tupple of `(max_accepted_children, max_pending_children)`
+    default fn children_limits(&self) -> (u32, u32) {
+        let data = self.data::<NestingData>();
+        (data.max_accepted_children, data.max_pending_children)
+    }
+
+    /// Check if the tokens of the collection can be nested into this collection
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    ///
+    /// # Result:
+    /// Returns `true` if the collection is approved
+    default fn is_allowed(&self, collection: CollectionId) -> bool {
+        self.ensure_allowed_child_collection(&collection).is_ok()
+    }
 }
 
 /// Event trait for Nesting
@@ -449,4 +1576,6 @@ where
         _child_token_id: &Id,
     ) {
     }
+
+    default fn _emit_children_order_set_event(&self, _parent: &Id, _children: Vec<ChildNft>) {}
 }
@@ -31,6 +31,20 @@ pub const STORAGE_NESTING_KEY: u32 = openbrush::storage_unique_key!(NestingData)
 pub struct NestingData {
     pub pending_children: Mapping<Id, Vec<ChildNft>>,
     pub accepted_children: Mapping<Id, Vec<ChildNft>>,
+    /// Parent token holding each token of this collection which is nested
+    pub parents: Mapping<Id, (CollectionId, Id)>,
+    /// Child collections approved by the collection owner
+    pub allowed_child_collections: Mapping<CollectionId, bool>,
+    /// Accept children from any collection
+    pub allow_all_child_collections: bool,
+    /// Maximum number of parents above a nested token, 0 for no limit
+    pub max_nesting_depth: u32,
+    /// Account which sent each pending child, the child is returned to it on rejection
+    pub child_senders: Mapping<ChildNft, AccountId>,
+    /// Maximum number of accepted children per parent token, 0 for no limit
+    pub max_accepted_children: u32,
+    /// Maximum number of pending children per parent token, 0 for no limit
+    pub max_pending_children: u32,
 }
 
 // Collection id is the address of child contract
@@ -39,6 +53,9 @@ pub type CollectionId = AccountId;
 // Nft is a tuple of collection and TokenId and refers to the Child nft
 pub type ChildNft = (CollectionId, Id);
 
+/// Maximum number of children handled by a single batch nesting call
+pub const MAX_BATCH_CHILDREN: u32 = 50;
+
 pub type BaseId = u32;
 pub type SlotId = u32;
 pub type PartId = u32;
@@ -78,7 +95,7 @@ pub struct MultiAssetData {
 }
 
 /// Part's details
-#[derive(scale::Encode, scale::Decode, SpreadLayout, PackedLayout, Default, Debug)]
+#[derive(scale::Encode, scale::Decode, SpreadLayout, PackedLayout, Default, Debug, Clone)]
 #[cfg_attr(
     feature = "std",
     derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
@@ -87,6 +104,15 @@ pub struct Asset {
     pub asset_id: AssetId,
     pub equippable_group_id: EquippableGroupId,
     pub asset_uri: String,
+
+    /// Catalog contract holding the Base, `None` if the Base of this collection is used
+    pub catalog_address: Option<AccountId>,
+
+    /// Base the parts of this asset belong to
+    pub base_id: Option<BaseId>,
+
+    /// List of fixed and slot parts composing this asset
+    pub part_ids: Vec<PartId>,
 }
 
 impl ink_storage::traits::PackedAllocate for Asset {
@@ -104,21 +130,30 @@ impl SpreadAllocate for Asset {
 
 pub const STORAGE_BASE_KEY: u32 = openbrush::storage_unique_key!(BaseData);
 
-/// The structure used to describe the Base
+/// The structure used to describe the registry of Bases
 #[derive(Default, Debug)]
 #[openbrush::upgradeable_storage(STORAGE_BASE_KEY)]
 pub struct BaseData {
-    /// List of all parts of Base.
-    pub part_ids: Vec<PartId>,
+    /// Counter for assigning new Bases.
+    pub next_base_id: BaseId,
+
+    /// List of all parts of each Base.
+    pub part_ids: Mapping<BaseId, Vec<PartId>>,
 
     /// Mapping for all part details.
-    pub parts: Mapping<PartId, Part>,
+    pub parts: Mapping<(BaseId, PartId), Part>,
 
-    /// Counter for assigning new parts to Base.
-    pub next_part_id: PartId,
+    /// Parts which can't be referenced by new assets.
+    pub retired_parts: Mapping<(BaseId, PartId), bool>,
 
-    /// Metadata for Base
-    pub base_metadata_uri: String,
+    /// Counter for assigning new parts to each Base.
+    pub next_part_id: Mapping<BaseId, PartId>,
+
+    /// Metadata for each Base
+    pub base_metadata_uri: Mapping<BaseId, String>,
+
+    /// Locked Bases can't be changed anymore
+    pub locked: Mapping<BaseId, bool>,
 }
 
 /// Part's details
@@ -155,3 +190,82 @@ pub enum PartType {
     Slot,
     Fixed,
 }
+
+pub const STORAGE_EQUIPPABLE_KEY: u32 = openbrush::storage_unique_key!(EquippableData);
+
+/// The structure used to describe equipped children
+#[derive(Default, Debug)]
+#[openbrush::upgradeable_storage(STORAGE_EQUIPPABLE_KEY)]
+pub struct EquippableData {
+    /// Mapping of (tokenId, assetId, slotPartId) to the equipped child.
+    pub equipment: Mapping<(Id, AssetId, SlotId), Equipment>,
+
+    /// Mapping of equipped child to (tokenId, assetId, slotPartId) it is equipped into.
+    pub equipped_children: Mapping<ChildNft, (Id, AssetId, SlotId)>,
+
+    /// Mapping of (equippableGroupId, parent collection, baseId) to the slot the group can be equipped into.
+    pub valid_parent_slots: Mapping<(EquippableGroupId, AccountId, BaseId), SlotId>,
+}
+
+/// Child equipped into a slot of the parent token's asset
+#[derive(scale::Encode, scale::Decode, SpreadLayout, PackedLayout, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Equipment {
+    /// (collection_id, token_id) of the equipped child
+    pub child_nft: ChildNft,
+
+    /// Asset of the child which is equipped
+    pub child_asset_id: AssetId,
+}
+
+/// Fixed part of the composed asset
+#[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FixedPart {
+    pub part_id: PartId,
+
+    /// Depth used for composing parts
+    pub z: u8,
+
+    /// Uri for this part
+    pub metadata_uri: String,
+}
+
+/// Slot part of the composed asset with the child equipped into it
+#[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SlotPart {
+    pub part_id: PartId,
+
+    /// Depth used for composing parts
+    pub z: u8,
+
+    /// Uri for this part, used when nothing is equipped
+    pub metadata_uri: String,
+
+    /// Child equipped into this slot
+    pub equipment: Option<Equipment>,
+
+    /// Uri of the equipped child's asset
+    pub child_asset_uri: Option<String>,
+}
+
+/// Full layered composition of a token's asset
+#[derive(scale::Encode, scale::Decode, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Composition {
+    /// Metadata for Base
+    pub base_metadata_uri: String,
+
+    /// Uri of the composed asset
+    pub asset_uri: String,
+
+    /// Fixed parts sorted by `z`
+    pub fixed_parts: Vec<FixedPart>,
+
+    /// Slot parts sorted by `z`
+    pub slot_parts: Vec<SlotPart>,
+}
@@ -13,54 +13,230 @@ use openbrush::{
     },
 };
 
+#[openbrush::wrapper]
+pub type BaseRef = dyn Base;
+
 /// Implement internal helper trait for Base
 pub trait Internal {
-    fn ensure_only_slot(&self, part_id: PartId) -> Result<Part, PSP34Error>;
+    /// Register a new Base with the given metadataURI. Return the ID of the new Base.
+    fn create_base(&mut self, base_metadata: String) -> BaseId;
+
+    /// Check if the Base is registered. Return error if it is not.
+    fn ensure_base_exists(&self, base_id: BaseId) -> Result<(), PSP34Error>;
+
+    fn ensure_only_slot(&self, base_id: BaseId, part_id: PartId) -> Result<Part, PSP34Error>;
+
+    /// Check if the Base exists and is not locked. Return error if it is locked.
+    fn ensure_not_locked(&self, base_id: BaseId) -> Result<(), PSP34Error>;
+
+    /// Check if the part is not retired. Return error if it is.
+    fn ensure_not_retired(&self, base_id: BaseId, part_id: PartId) -> Result<(), PSP34Error>;
+
+    /// Check if the Base is registered in the catalog contract, or in this collection if
+    /// `catalog_address` is `None`.
+    fn catalog_base_exists(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+    ) -> Result<bool, PSP34Error>;
+
+    /// Get the part from the catalog contract, or from the Bases of this collection if `catalog_address` is `None`.
+    fn get_catalog_part(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<Option<Part>, PSP34Error>;
+
+    /// Check if the part is retired in the catalog contract, or in the Bases of this collection if
+    /// `catalog_address` is `None`.
+    fn is_catalog_part_retired(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<bool, PSP34Error>;
+
+    /// Get the Base metadataURI from the catalog contract, or from the Bases of this collection if
+    /// `catalog_address` is `None`.
+    fn get_catalog_metadata(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: BaseId,
+    ) -> Result<String, PSP34Error>;
 }
 /// Trait definitions for Base
 #[openbrush::trait_definition]
 pub trait Base {
+    /// Register a new Base with its own parts and metadata.
+    /// Returns the ID of the new Base.
+    #[ink(message)]
+    fn add_base(&mut self, base_metadata: String) -> Result<BaseId, PSP34Error>;
+
     /// Add one or more parts to the base
     #[ink(message)]
-    fn add_part_list(&mut self, parts: Vec<Part>) -> Result<(), PSP34Error>;
+    fn add_part_list(&mut self, base_id: BaseId, parts: Vec<Part>) -> Result<(), PSP34Error>;
 
     /// Add collection address(es) that can be used to equip given `PartId`.
     #[ink(message)]
     fn add_equippable_addresses(
         &mut self,
+        base_id: BaseId,
         part_id: PartId,
         equippable_address: Vec<AccountId>,
     ) -> Result<(), PSP34Error>;
 
     /// Remove list of equippable addresses for given Part
     #[ink(message)]
-    fn reset_equippable_addresses(&mut self, part_id: PartId) -> Result<(), PSP34Error>;
+    fn reset_equippable_addresses(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Remove a single collection address from the equippable list of given `PartId`.
+    #[ink(message)]
+    fn remove_equippable_address(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+        equippable_address: AccountId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Update the depth and the metadataURI of the given `PartId`.
+    #[ink(message)]
+    fn update_part(
+        &mut self,
+        base_id: BaseId,
+        part_id: PartId,
+        z: u8,
+        metadata_uri: String,
+    ) -> Result<(), PSP34Error>;
+
+    /// Retire the given `PartId` so it can't be referenced by new assets or equipped into.
+    /// Assets already referencing the part keep rendering it.
+    #[ink(message)]
+    fn retire_part(&mut self, base_id: BaseId, part_id: PartId) -> Result<(), PSP34Error>;
 
     /// Sets the is_equippable_by_all flag to true, meaning that any collection may be equipped into the `PartId`
     #[ink(message)]
-    fn set_equippable_by_all(&mut self, part_id: PartId) -> Result<(), PSP34Error>;
+    fn set_equippable_by_all(&mut self, base_id: BaseId, part_id: PartId)
+        -> Result<(), PSP34Error>;
 
     //// Set the Base metadataURI.
     #[ink(message)]
-    fn setup_base(&mut self, base_metadata: String) -> Result<(), PSP34Error>;
+    fn setup_base(&mut self, base_id: BaseId, base_metadata: String) -> Result<(), PSP34Error>;
+
+    /// Lock the Base. Parts, equippable addresses and metadata can't be changed afterwards.
+    #[ink(message)]
+    fn lock_base(&mut self, base_id: BaseId) -> Result<(), PSP34Error>;
+
+    /// Get the list of all Bases.
+    #[ink(message)]
+    fn get_bases(&self) -> Vec<BaseId>;
 
     //// Get the Base metadataURI.
     #[ink(message)]
-    fn get_base_metadata(&self) -> PreludeString;
+    fn get_base_metadata(&self, base_id: BaseId) -> PreludeString;
 
     /// Get the list of all parts.
     #[ink(message)]
-    fn get_parts_count(&self) -> PartId;
+    fn get_parts_count(&self, base_id: BaseId) -> PartId;
 
     /// Get the part details for the given PartId.
     #[ink(message)]
-    fn get_part(&self, part_id: PartId) -> Option<Part>;
+    fn get_part(&self, base_id: BaseId, part_id: PartId) -> Option<Part>;
 
     /// Check whether the given address is allowed to equip the desired `PartId`.
     #[ink(message)]
-    fn is_equippable(&self, part_id: PartId, target_address: AccountId) -> bool;
+    fn is_equippable(&self, base_id: BaseId, part_id: PartId, target_address: AccountId) -> bool;
 
     /// Checks if the given `PartId` can be equipped by any collection
     #[ink(message)]
-    fn is_equippable_by_all(&self, part_id: PartId) -> bool;
+    fn is_equippable_by_all(&self, base_id: BaseId, part_id: PartId) -> bool;
+
+    /// Checks if the given `PartId` is retired
+    #[ink(message)]
+    fn is_part_retired(&self, base_id: BaseId, part_id: PartId) -> bool;
+
+    /// Checks if the Base is locked
+    #[ink(message)]
+    fn is_locked(&self, base_id: BaseId) -> bool;
+}
+
+/// Trait definitions for Base ink events
+#[openbrush::trait_definition]
+pub trait BaseEvents {
+    /// Used to notify listeners that a new Base is registered.
+    /// # Arguments:
+    /// * base_id ID of the new Base
+    /// * base_metadata metadataURI of the new Base
+    fn _emit_base_added_event(&self, base_id: &BaseId, base_metadata: &String);
+
+    /// Used to notify listeners that a part is added to the Base.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the added part
+    /// * part details of the added part
+    fn _emit_part_added_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part);
+
+    /// Used to notify listeners that collection addresses are allowed to be equipped into the part.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the slot part
+    /// * equippable_addresses collection addresses added to the equippable list
+    fn _emit_equippable_addresses_added_event(
+        &self,
+        base_id: &BaseId,
+        part_id: &PartId,
+        equippable_addresses: &[AccountId],
+    );
+
+    /// Used to notify listeners that a collection address is removed from the equippable list.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the slot part
+    /// * equippable_address collection address removed from the equippable list
+    fn _emit_equippable_address_removed_event(
+        &self,
+        base_id: &BaseId,
+        part_id: &PartId,
+        equippable_address: &AccountId,
+    );
+
+    /// Used to notify listeners that the equippable list of the part is cleared.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the slot part
+    fn _emit_equippable_addresses_reset_event(&self, base_id: &BaseId, part_id: &PartId);
+
+    /// Used to notify listeners that any collection can be equipped into the part.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the slot part
+    fn _emit_equippable_to_all_set_event(&self, base_id: &BaseId, part_id: &PartId);
+
+    /// Used to notify listeners that the depth or the metadataURI of the part changed.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the updated part
+    /// * part details of the updated part
+    fn _emit_part_updated_event(&self, base_id: &BaseId, part_id: &PartId, part: &Part);
+
+    /// Used to notify listeners that the part is retired.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * part_id ID of the retired part
+    fn _emit_part_retired_event(&self, base_id: &BaseId, part_id: &PartId);
+
+    /// Used to notify listeners that the Base metadataURI is set.
+    /// # Arguments:
+    /// * base_id ID of the Base
+    /// * base_metadata new metadataURI of the Base
+    fn _emit_base_metadata_set_event(&self, base_id: &BaseId, base_metadata: &String);
+
+    /// Used to notify listeners that the Base is locked and can't be changed anymore.
+    /// # Arguments:
+    /// * base_id ID of the locked Base
+    fn _emit_base_locked_event(&self, base_id: &BaseId);
 }
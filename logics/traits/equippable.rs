@@ -0,0 +1,234 @@
+//! Trait definitions for Equippable module
+use crate::impls::rmrk::types::*;
+use openbrush::{
+    contracts::psp34::{
+        Id,
+        PSP34Error,
+    },
+    traits::{
+        AccountId,
+        String,
+    },
+};
+
+#[openbrush::wrapper]
+pub type EquippableRef = dyn Equippable;
+
+/// Trait definitions for Equippable ink! messages
+#[openbrush::trait_definition]
+pub trait Equippable {
+    /// Used to equip a child nft into a token.
+    /// # Requirements:
+    ///  * The caller must own the parent token
+    ///  * `child_nft` must be an accepted child of the parent token
+    ///  * `child_asset_id` must be accepted by the child token
+    ///  * `asset_id` must be accepted by the parent token
+    ///  * `slot_part_id` must be a Slot part of the asset which allows the child collection to be equipped
+    ///  * The slot must not be in use and the child must not already be equipped
+    ///  * The equippable group of the child asset must be valid for this collection and slot
+    /// # Arguments
+    ///  * `token_id` ID of the token that had an asset equipped
+    ///  * `asset_id` ID of the asset associated with the token we are equipping into
+    ///  * `slot_part_id` ID of the slot part that we are using to equip
+    ///  * `child_nft` (collection_id, token_id) of the child instance being equipped
+    ///  * `child_asset_id` ID of the asset that we are equipping
+    /// Emits a {ChildAssetEquipped} event.
+    #[ink(message)]
+    fn equip(
+        &mut self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+        child_nft: ChildNft,
+        child_asset_id: AssetId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Used to unequip child from parent token.
+    /// # Requirements:
+    ///  * The caller must own the parent token
+    ///  * There must be a child equipped into the slot
+    /// # Arguments
+    ///  * `token_id` ID of the token that had an asset unequipped
+    ///  * `asset_id` ID of the asset associated with the token we are unequipping from
+    ///  * `slot_part_id` ID of the slot part that we are unequipping
+    /// Emits a {ChildAssetUnequipped} event.
+    #[ink(message)]
+    fn unequip(
+        &mut self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Used to unequip the child and remove it from the parent token in one call.
+    /// # Requirements:
+    ///  * The caller must own the parent token
+    ///  * `child_nft` must be an accepted child of the parent token
+    /// # Arguments
+    ///  * `parent_token_id` ID of the parent token
+    ///  * `child_nft` (collection_id, token_id) of the child instance
+    /// # Result:
+    /// Child is unequipped if it was equipped and the ownership of child NFT is transferred
+    /// to the root owner of the parent NFT.
+    /// Emits a {ChildAssetUnequipped} event if the child was equipped and a {ChildRemoved} event.
+    #[ink(message)]
+    fn unequip_and_remove(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Used to retrieve the child equipped into the slot of the token's asset.
+    #[ink(message)]
+    fn get_equipment(
+        &self,
+        token_id: Id,
+        asset_id: AssetId,
+        slot_part_id: SlotId,
+    ) -> Option<Equipment>;
+
+    /// Check whether the child is equipped into any slot of its parent.
+    #[ink(message)]
+    fn is_child_equipped(&self, child_nft: ChildNft) -> bool;
+
+    /// Used to compose the layers of the token's asset for rendering.
+    /// # Requirements:
+    ///  * `asset_id` must be accepted by the token
+    /// # Arguments
+    ///  * `token_id` ID of the token to compose
+    ///  * `asset_id` ID of the asset to compose
+    /// # Result:
+    /// Base metadata, asset uri, fixed parts and slot parts with equipped children.
+    /// Parts are sorted by `z`.
+    #[ink(message)]
+    fn compose(&self, token_id: Id, asset_id: AssetId) -> Result<Composition, PSP34Error>;
+
+    /// Used to declare that the assets belonging to a given `equippable_group_id` are equippable
+    /// into the `Slot` associated with the `slot_part_id` of the `base_id` Base, for the collection
+    /// at the specified `parent_address`.
+    /// Each Base numbers its parts independently, so a group is valid for one Base at a time.
+    /// # Arguments
+    ///  * `equippable_group_id` ID of the equippable group
+    ///  * `parent_address` Address of the parent into which the equippable group can be equipped into
+    ///  * `base_id` ID of the Base used by the parent asset
+    ///  * `slot_part_id` ID of the Slot part that the items belonging to the equippable group can be
+    ///     equipped into
+    /// Emits a {ValidParentEquippableGroupIdSet} event.
+    #[ink(message)]
+    fn set_valid_parent_for_equippable_group(
+        &mut self,
+        equippable_group_id: EquippableGroupId,
+        parent_address: AccountId,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Check whether the assets of the `equippable_group_id` can be equipped into the
+    /// `slot_part_id` of the `base_id` Base for the collection at `parent_address`.
+    #[ink(message)]
+    fn is_valid_parent_for_equippable_group(
+        &self,
+        equippable_group_id: EquippableGroupId,
+        parent_address: AccountId,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+    ) -> bool;
+}
+
+/// Trait definitions for Equippable helper functions
+pub trait Internal {
+    /// Check if the caller is the root owner of the parent token.
+    fn ensure_parent_owner(&self, token_id: &Id) -> Result<(), PSP34Error>;
+
+    /// Check if the asset is accepted by the parent token.
+    fn ensure_parent_asset(&self, token_id: &Id, asset_id: &AssetId) -> Result<(), PSP34Error>;
+
+    /// Check if the slot is one of the parts composing the parent's asset. Return the asset.
+    fn ensure_asset_slot(
+        &self,
+        asset_id: &AssetId,
+        slot_part_id: SlotId,
+    ) -> Result<Asset, PSP34Error>;
+
+    /// Check if the child is accepted by the parent token.
+    fn ensure_accepted_child(&self, token_id: &Id, child_nft: &ChildNft) -> Result<(), PSP34Error>;
+
+    /// Check if the child collection can be equipped into the slot of the asset's Base.
+    fn ensure_equippable_slot(
+        &self,
+        asset: &Asset,
+        slot_part_id: SlotId,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Check if the child is not equipped. Return error if it is.
+    fn ensure_not_equipped(&self, child_nft: &ChildNft) -> Result<(), PSP34Error>;
+
+    /// Get the uri of the equipped child's asset.
+    fn get_child_asset_uri(&self, equipment: &Equipment) -> Result<Option<String>, PSP34Error>;
+
+    /// Check if the child has the asset accepted and get the equippable group of the asset.
+    fn get_child_asset_group(
+        &self,
+        child_nft: &ChildNft,
+        child_asset_id: &AssetId,
+    ) -> Result<EquippableGroupId, PSP34Error>;
+
+    /// Check if the equippable group of the child asset allows it to be equipped into the slot.
+    fn ensure_equippable_group(
+        &self,
+        base_id: BaseId,
+        slot_part_id: SlotId,
+        child_nft: &ChildNft,
+        child_asset_id: &AssetId,
+    ) -> Result<(), PSP34Error>;
+}
+
+/// Trait definitions for Equippable ink events
+#[openbrush::trait_definition]
+pub trait EquippableEvents {
+    /// Used to notify listeners that a child's asset has been equipped into one of its parent assets.
+    /// # Arguments:
+    /// * token_id ID of the token that had an asset equipped
+    /// * asset_id ID of the asset associated with the token we are equipping into
+    /// * slot_part_id ID of the slot we are using to equip
+    /// * child_nft (collection_id, token_id) of the child
+    /// * child_asset_id ID of the asset that we are equipping
+    fn _emit_child_asset_equipped_event(
+        &self,
+        token_id: &Id,
+        asset_id: &AssetId,
+        slot_part_id: &SlotId,
+        child_nft: &ChildNft,
+        child_asset_id: &AssetId,
+    );
+
+    /// Used to notify listeners that a child's asset has been unequipped from one of its parent assets.
+    /// # Arguments:
+    /// * token_id ID of the token that had an asset unequipped
+    /// * asset_id ID of the asset associated with the token we are unequipping
+    /// * slot_part_id ID of the slot we are unequipping from
+    fn _emit_child_asset_unequipped_event(
+        &self,
+        token_id: &Id,
+        asset_id: &AssetId,
+        slot_part_id: &SlotId,
+    );
+
+    /// Used to notify listeners that the assets belonging to a `equippable_group_id` have been
+    /// marked as equippable into a given slot and parent
+    /// # Arguments:
+    /// * equippable_group_id ID of the equippable group being marked as equippable into the slot
+    /// * parent_address Address of the collection into which the parts belonging to the equippable
+    ///     group can be equipped
+    /// * base_id ID of the Base of the slot part
+    /// * slot_part_id ID of the slot part of the base into which the parts belonging to the
+    ///     equippable group can be equipped
+    fn _emit_valid_parent_equippable_group_id_set_event(
+        &self,
+        equippable_group_id: &EquippableGroupId,
+        parent_address: &AccountId,
+        base_id: &BaseId,
+        slot_part_id: &SlotId,
+    );
+}
@@ -1,7 +1,10 @@
 //! RMRK minting traits
 
 use openbrush::{
-    contracts::psp34::PSP34Error,
+    contracts::psp34::{
+        Id,
+        PSP34Error,
+    },
     traits::AccountId,
 };
 
@@ -27,4 +30,25 @@ pub trait Minting {
     /// Mint one or more tokens.
     #[ink(message, payable)]
     fn mint(&mut self, to: AccountId, mint_amount: u64) -> Result<(), PSP34Error>;
+
+    /// Mint one or more tokens directly into a parent token.
+    /// If the parent is in this collection, every new token is minted to this contract and
+    /// nested under `parent_token_id` right away. The children are accepted if the caller owns
+    /// the parent, pending otherwise.
+    /// If the parent is in another collection, every new token is minted to the caller and added
+    /// through the parent's `add_child()`, so it is pending until the parent owner accepts it
+    /// and a rejected token returns to the caller.
+    /// The parent, the allowed collections and the children limits are checked before minting.
+    ///
+    /// # Arguments:
+    /// * `parent_collection`: collection of the parent token
+    /// * `parent_token_id`: is the tokenId of the parent NFT
+    /// * `mint_amount`: number of tokens to mint
+    #[ink(message, payable)]
+    fn nest_mint(
+        &mut self,
+        parent_collection: AccountId,
+        parent_token_id: Id,
+        mint_amount: u64,
+    ) -> Result<(), PSP34Error>;
 }
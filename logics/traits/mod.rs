@@ -1,4 +1,5 @@
 pub mod base;
+pub mod equippable;
 pub mod minting;
 pub mod multiasset;
 pub mod nesting;
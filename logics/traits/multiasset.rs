@@ -21,14 +21,22 @@ pub trait MultiAsset {
     /// Used to add a asset entry.
     /// The ID of the asset is automatically assigned to be the next available asset ID.
     /// # Arguments
+    ///  * `id` ID of the new asset
+    ///  * `equippable_group_id` ID of the equippable group the asset belongs to
+    ///  * `catalog_address` Catalog contract holding the Base, `None` to use the Bases of this collection
+    ///  * `base_id` ID of the Base which holds the parts of the asset
     ///  * `asset_uri` Uri for the new asset
+    ///  * `part_ids` List of fixed and slot parts composing the asset. Parts must exist in the Base
     /// Emits an {AssetSet} event.
     #[ink(message)]
     fn add_asset_entry(
         &mut self,
         id: AssetId,
         equippable_group_id: EquippableGroupId,
+        catalog_address: Option<AccountId>,
+        base_id: Option<BaseId>,
         asset_uri: String,
+        part_ids: Vec<PartId>,
     ) -> Result<(), PSP34Error>;
 
     /// Used to add an asset to a token.
@@ -112,6 +120,18 @@ pub trait MultiAsset {
     /// Remove the assets for the list of token assets
     #[ink(message)]
     fn remove_asset(&mut self, token_id: Id, asset_id: AssetId) -> Result<(), PSP34Error>;
+
+    /// Used to retrieve the equippable group the asset belongs to
+    #[ink(message)]
+    fn get_asset_equippable_group(&self, asset_id: AssetId) -> Option<EquippableGroupId>;
+
+    /// Used to retrieve the fixed parts composing the asset
+    #[ink(message)]
+    fn get_asset_fixed_parts(&self, asset_id: AssetId) -> Result<Vec<PartId>, PSP34Error>;
+
+    /// Used to retrieve the slot parts composing the asset
+    #[ink(message)]
+    fn get_asset_slot_parts(&self, asset_id: AssetId) -> Result<Vec<PartId>, PSP34Error>;
 }
 
 /// Trait definitions for Resource helper functions
@@ -120,11 +140,29 @@ pub trait Internal {
     /// Check if asset is already added.
     fn asset_id_exists(&self, asset_id: AssetId) -> Option<String>;
 
+    /// Get the asset entry for the given asset_id.
+    fn get_asset_entry(&self, asset_id: AssetId) -> Result<Asset, PSP34Error>;
+
+    /// Check if the Base is registered and all the parts exist in it and are not retired.
+    fn ensure_asset_parts(
+        &self,
+        catalog_address: Option<AccountId>,
+        base_id: Option<BaseId>,
+        part_ids: &[PartId],
+    ) -> Result<(), PSP34Error>;
+
+    /// Get the parts of the given type composing the asset.
+    fn get_asset_parts_of_type(
+        &self,
+        asset_id: AssetId,
+        part_type: PartType,
+    ) -> Result<Vec<PartId>, PSP34Error>;
+
     /// TODO duplicated. find common module for this method
     fn ensure_exists(&self, id: &Id) -> Result<AccountId, PSP34Error>;
 
-    /// Ensure that the caller is the token owner
-    fn ensure_token_owner(&self, token_owner: AccountId) -> Result<(), PSP34Error>;
+    /// Ensure that the caller is the root owner of the token
+    fn ensure_token_owner(&self, token_id: &Id) -> Result<(), PSP34Error>;
 
     /// Check if asset is already accepted. Return error if it is
     fn ensure_not_accepted(&self, token_id: &Id, asset_id: &AssetId) -> Result<(), PSP34Error>;
@@ -157,6 +195,12 @@ pub trait Internal {
         token_id: &Id,
         asset_id: &AssetId,
     ) -> Result<(), PSP34Error>;
+
+    /// Remove all accepted and pending assets of the token
+    fn remove_token_assets(&mut self, token_id: &Id);
+
+    /// Remove all pending assets of the token
+    fn remove_pending_assets(&mut self, token_id: &Id);
 }
 
 /// Trait definitions for Resource ink events
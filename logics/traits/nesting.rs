@@ -1,5 +1,6 @@
 //! Trait definitions for Nesting module
 use crate::impls::rmrk::types::*;
+use ink_prelude::vec::Vec;
 use openbrush::{
     contracts::psp34::{
         Id,
@@ -26,6 +27,10 @@ pub trait Nesting {
     /// * `to_parent_token_id` must exist.
     /// * `child_token_id` must exist.
     /// * There cannot be two identical children.
+    /// * Caller is the owner of a child from this collection or an operator approved by it.
+    /// * A child from another collection has approved this contract.
+    /// * The child is not an ancestor of `to_parent_token_id`.
+    /// * The child does not exceed the maximum nesting depth.
     ///
     /// # Arguments:
     /// * `to_parent_token_id`: is the tokenId of the parent NFT. The receiver of child.
@@ -38,11 +43,60 @@ pub trait Nesting {
     #[ink(message)]
     fn add_child(&mut self, parent_token_id: Id, child_nft: ChildNft) -> Result<(), PSP34Error>;
 
+    /// Add several child NFTs to the NFT in this collection, as `add_child()` does for each.
+    /// Applied atomically, the call fails on the first child which can't be added.
+    ///
+    /// # Requirements:
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * Each child meets the requirements of `add_child()`
+    /// * The whole batch is checked before the first child is moved
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT. The receiver of children.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Ownership of the children will be transferred to this contract (cross contract call)
+    /// On success emitts `RmrkEvent::ChildAdded` for each child
+    /// On success emitts `RmrkEvent::ChildAccepted` for each child - only if caller is parent owner
+    #[ink(message)]
+    fn add_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Nest a token of this collection into a parent token, in this or another RMRK collection.
+    /// Replaces the `approve()` + `add_child()` sequence.
+    ///
+    /// # Requirements:
+    /// * Caller is the owner of the token or an operator approved by it
+    /// * `to_parent_id` must exist in `to_collection`
+    /// * `to_collection` allows children of this collection and the parent has room for one more
+    ///
+    /// # Arguments:
+    /// * `to_collection`: address of the parent contract
+    /// * `to_parent_id`: tokenId of the parent NFT. The receiver of the token.
+    /// * `token_id`: tokenId of the token to nest
+    ///
+    /// # Result:
+    /// Ownership of the token is transferred to the parent contract (cross contract call)
+    /// On success emitts `RmrkEvent::ChildAdded` in the parent contract
+    /// On failure the token stays with its owner and the approval of the parent contract is revoked
+    #[ink(message)]
+    fn nest_transfer(
+        &mut self,
+        to_collection: AccountId,
+        to_parent_id: Id,
+        token_id: Id,
+    ) -> Result<(), PSP34Error>;
+
     /// Remove a child NFT (from different collection) from token_id in this collection.
     /// The status of added child is `Pending` if caller is not owner of child NFT
     /// The status of added child is `Accepted` if caller is is owner of child NFT
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Accepted`
     ///
     /// # Arguments:
@@ -50,7 +104,7 @@ pub trait Nesting {
     /// * `child_nft`: (collection_id, token_id) of the child instance.
     ///
     /// # Result:
-    /// Ownership of child NFT will be transferred to parent NFT owner (cross contract call)
+    /// Ownership of child NFT will be transferred to the root owner of parent NFT (cross contract call)
     /// On success emitts `RmrkEvent::ChildRemoved`
     #[ink(message)]
     fn remove_child(&mut self, parent_token_id: Id, child_nft: ChildNft) -> Result<(), PSP34Error>;
@@ -58,6 +112,7 @@ pub trait Nesting {
     /// Accept a child NFT (from different collection) to be owned by parent token.
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Pending`
     ///
     /// # Arguments:
@@ -70,9 +125,33 @@ pub trait Nesting {
     #[ink(message)]
     fn accept_child(&mut self, parent_token_id: Id, child_nft: ChildNft) -> Result<(), PSP34Error>;
 
+    /// Accept several pending children of the parent token.
+    /// Applied atomically, the call fails on the first child which can't be accepted.
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * The status of each child is `Pending`
+    /// * The whole batch is checked before the first child is changed
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Children are moved from pending to accepted
+    /// On success emitts `RmrkEvent::ChildAccepted` for each child
+    #[ink(message)]
+    fn accept_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error>;
+
     /// Reject a child NFT (from different collection).
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
     /// * The status of the child is `Pending`
     ///
     /// # Arguments:
@@ -80,15 +159,61 @@ pub trait Nesting {
     /// * `child_nft`: (collection_id, token_id) of the child instance.
     ///
     /// # Result:
-    /// Child Nft is removed from pending
+    /// Child Nft is removed from pending and returned to the account which sent it (cross contract call)
     /// On success emitts `RmrkEvent::ChildRejected`
     #[ink(message)]
     fn reject_child(&mut self, parent_token_id: Id, child_nft: ChildNft) -> Result<(), PSP34Error>;
 
+    /// Reject several pending children of the parent token.
+    /// Applied atomically, the call fails on the first child which can't be rejected.
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * At most `MAX_BATCH_CHILDREN` children, each named once
+    /// * The status of each child is `Pending`
+    /// * The whole batch is checked before the first child is changed
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: (collection_id, token_id) of the child instances.
+    ///
+    /// # Result:
+    /// Children are removed from pending and returned to the accounts which sent them (cross contract call)
+    /// On success emitts `RmrkEvent::ChildRejected` for each child
+    #[ink(message)]
+    fn reject_children(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Reject all pending children of the parent token.
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * The number of pending children is not above `max_rejections`
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `max_rejections`: maximum number of children expected to be rejected
+    ///
+    /// # Result:
+    /// Ownership of the children is returned to the accounts which sent them (cross contract call)
+    /// On success emitts `RmrkEvent::ChildRejected` for each child
+    #[ink(message)]
+    fn reject_all_children(
+        &mut self,
+        parent_token_id: Id,
+        max_rejections: u32,
+    ) -> Result<(), PSP34Error>;
+
     /// Transfer the child NFT from one parent to another (in this collection).
     ///
     /// # Requirements:
+    /// * Caller is the root owner of the current parent token or an operator approved by it
     /// * The status of the child is `Accepted`
+    /// * The child is not an ancestor of `new_parent`
+    /// * The child does not exceed the maximum nesting depth
     ///
     /// # Arguments:
     /// * `current_parent`: current parent tokenId which holds child nft
@@ -98,7 +223,7 @@ pub trait Nesting {
     /// # Result:
     /// Ownership of child NFT will be transferred to this contract (cross contract call)
     /// On success emitts `RmrkEvent::ChildAdded`
-    /// On success emitts `RmrkEvent::ChildAccepted` - only if caller is already owner of child NFT
+    /// On success emitts `RmrkEvent::ChildAccepted` - only if both parents have the same root owner
     #[ink(message)]
     fn transfer_child(&mut self, from: Id, to: Id, child_nft: ChildNft) -> Result<(), PSP34Error>;
 
@@ -110,6 +235,222 @@ pub trait Nesting {
     /// Returns the tupple of `(accepted_children, pending_children)` count
     #[ink(message)]
     fn children_balance(&self, parent_token_id: Id) -> Result<(u64, u64), PSP34Error>;
+
+    /// Reorder the accepted children of the parent token.
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the parent token or an operator approved by it
+    /// * `children` contains exactly the accepted children of the parent token
+    ///
+    /// # Arguments:
+    /// * `parent_token_id`: is the tokenId of the parent NFT.
+    /// * `children`: accepted children of the parent in the new order
+    ///
+    /// # Result:
+    /// On success emitts `RmrkEvent::ChildrenOrderSet`
+    #[ink(message)]
+    fn set_children_order(
+        &mut self,
+        parent_token_id: Id,
+        children: Vec<ChildNft>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Read a page of the accepted children of the parent token.
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `offset`: index of the first child to return
+    /// * `limit`: maximum number of children to return
+    ///
+    /// # Result:
+    /// Returns the (collection_id, token_id) of the accepted children
+    #[ink(message)]
+    fn get_accepted_children(
+        &self,
+        parent_token_id: Id,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ChildNft>, PSP34Error>;
+
+    /// Read a page of the pending children of the parent token.
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `offset`: index of the first child to return
+    /// * `limit`: maximum number of children to return
+    ///
+    /// # Result:
+    /// Returns the (collection_id, token_id) of the pending children
+    #[ink(message)]
+    fn get_pending_children(
+        &self,
+        parent_token_id: Id,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ChildNft>, PSP34Error>;
+
+    /// Read the position of the child among the accepted children of the parent token.
+    /// # Arguments:
+    /// * `parent_token_id`: parent tokenId to check
+    /// * `child_nft`: (collection_id, token_id) of the child instance.
+    ///
+    /// # Result:
+    /// Returns the index of the child, `None` if the child is not accepted by the parent
+    #[ink(message)]
+    fn child_index(
+        &self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<Option<u32>, PSP34Error>;
+
+    /// Burn the token together with its accepted children.
+    ///
+    /// # Requirements:
+    /// * Caller is the root owner of the token or an operator approved by it
+    /// * Accepted children are not equipped
+    /// * A nested token is accepted by its parent and not equipped. It is removed from the parent.
+    /// * A token nested into another collection is burned only by that collection
+    ///
+    /// # Arguments:
+    /// * `token_id`: tokenId of the token to burn
+    /// * `max_recursive_burns`: maximum number of children which can be burned, at any depth
+    ///
+    /// # Result:
+    /// Children from RMRK collections are burned recursively (cross contract call).
+    /// Children which can't be burned are transferred to the root owner of the token.
+    /// Pending children are returned to the accounts which sent them.
+    /// Returns the number of burned children.
+    /// On success emitts `RmrkEvent::ChildRemoved` for each accepted child
+    #[ink(message)]
+    fn burn(&mut self, token_id: Id, max_recursive_burns: u32) -> Result<u32, PSP34Error>;
+
+    /// Read the number of levels of children below the token.
+    /// Accepted and pending children are counted.
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns 0 if the token has no children
+    #[ink(message)]
+    fn children_depth(&self, token_id: Id) -> Result<u32, PSP34Error>;
+
+    /// Read the direct owner of the token.
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the owner account and, if the token is nested, the tokenId of the parent
+    /// in the owner collection
+    #[ink(message)]
+    fn direct_owner_of(&self, token_id: Id) -> Result<(AccountId, Option<Id>), PSP34Error>;
+
+    /// Read the root owner of the token, walking up the parent tokens (cross contract call).
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the owner of the top most parent token
+    #[ink(message)]
+    fn root_owner_of(&self, token_id: Id) -> Result<AccountId, PSP34Error>;
+
+    /// Read the parent token holding the token.
+    /// # Arguments:
+    /// * `token_id`: tokenId to check
+    ///
+    /// # Result:
+    /// Returns the parent (collection_id, token_id) if the token is nested, `None` otherwise
+    #[ink(message)]
+    fn parent_of(&self, token_id: Id) -> Option<(CollectionId, Id)>;
+
+    /// Record the parent of the token once it is nested into the caller collection.
+    /// Called by the parent collection when the token is added or moved between its tokens.
+    ///
+    /// # Requirements:
+    /// * Caller is the owner of the token, i.e. the parent collection
+    ///
+    /// # Arguments:
+    /// * `token_id`: tokenId of the nested token
+    /// * `parent_token_id`: tokenId of the parent in the caller collection, `None` to clear it
+    #[ink(message)]
+    fn set_parent(&mut self, token_id: Id, parent_token_id: Option<Id>) -> Result<(), PSP34Error>;
+
+    /// Approve a child collection to be nested into the tokens of this collection.
+    /// Tokens of this collection are always allowed.
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    #[ink(message)]
+    fn add_allowed_child_collection(&mut self, collection: CollectionId) -> Result<(), PSP34Error>;
+
+    /// Revoke the approval of a child collection.
+    /// Children which are already nested can still be removed.
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    #[ink(message)]
+    fn remove_allowed_child_collection(
+        &mut self,
+        collection: CollectionId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Accept children from any collection, regardless of the approved ones.
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `allow_all`: `true` to accept any collection, `false` to accept only approved ones
+    #[ink(message)]
+    fn set_allow_all_child_collections(&mut self, allow_all: bool) -> Result<(), PSP34Error>;
+
+    /// Set the maximum number of parents above a nested token.
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `max_nesting_depth`: maximum depth, 0 for no limit
+    #[ink(message)]
+    fn set_max_nesting_depth(&mut self, max_nesting_depth: u32) -> Result<(), PSP34Error>;
+
+    /// Read the maximum number of parents above a nested token, 0 for no limit.
+    #[ink(message)]
+    fn max_nesting_depth(&self) -> u32;
+
+    /// Set the maximum number of accepted and pending children per parent token.
+    ///
+    /// # Requirements:
+    /// * Caller is the collection owner
+    ///
+    /// # Arguments:
+    /// * `max_accepted_children`: maximum number of accepted children, 0 for no limit
+    /// * `max_pending_children`: maximum number of pending children, 0 for no limit
+    #[ink(message)]
+    fn set_children_limits(
+        &mut self,
+        max_accepted_children: u32,
+        max_pending_children: u32,
+    ) -> Result<(), PSP34Error>;
+
+    /// Read the maximum number of children per parent token, 0 for no limit.
+    ///
+    /// # Result:
+    /// Returns the tupple of `(max_accepted_children, max_pending_children)`
+    #[ink(message)]
+    fn children_limits(&self) -> (u32, u32);
+
+    /// Check if the tokens of the collection can be nested into this collection.
+    /// # Arguments:
+    /// * `collection`: address of the child contract
+    ///
+    /// # Result:
+    /// Returns `true` if the collection is approved
+    #[ink(message)]
+    fn is_allowed(&self, collection: CollectionId) -> bool;
 }
 
 /// Trait definitions for Nesting ink events
@@ -141,6 +482,9 @@ pub trait NestingEvents {
         child_collection_address: &AccountId,
         child_token_id: &Id,
     );
+
+    /// Emit ChildrenOrderSet event.
+    fn _emit_children_order_set_event(&self, parent: &Id, children: Vec<ChildNft>);
 }
 
 /// Trait implementation for Internal Nesting functions.
@@ -151,8 +495,26 @@ pub trait Internal {
     /// Check if child is already pending.
     fn pending(&self, parent_token_id: &Id, child_nft: &ChildNft) -> Result<(), PSP34Error>;
 
+    /// Check that the child is accepted by the parent token.
+    fn ensure_in_accepted(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Check that the child is pending on the parent token.
+    fn ensure_in_pending(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
     /// Add the child to the list of accepted children.
-    fn add_to_accepted(&mut self, parent_token_id: Id, child_nft: ChildNft);
+    fn add_to_accepted(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+    ) -> Result<(), PSP34Error>;
 
     /// Remove the child to the list of accepted children.
     fn remove_accepted(
@@ -161,8 +523,13 @@ pub trait Internal {
         child_nft: &ChildNft,
     ) -> Result<(), PSP34Error>;
 
-    /// Add the child to the list of pending children.
-    fn add_to_pending(&mut self, parent_token_id: Id, child_nft: ChildNft);
+    /// Add the child to the list of pending children and record its sender.
+    fn add_to_pending(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error>;
 
     /// Remove the child to the list of pending children.
     fn remove_from_pending(
@@ -171,20 +538,154 @@ pub trait Internal {
         child_nft: &ChildNft,
     ) -> Result<(), PSP34Error>;
 
+    /// Check that `amount` more children fit in the accepted or pending list of the parent token.
+    fn ensure_children_capacity(
+        &self,
+        parent_token_id: &Id,
+        accepted: bool,
+        amount: usize,
+    ) -> Result<(), PSP34Error>;
+
+    /// Insert the child owned by this contract under the parent token.
+    /// The child goes to the accepted list if `accepted`, to the pending list otherwise.
+    fn nest_child(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        accepted: bool,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Check that the child can be added to the parent token and that the caller may hand it over.
+    /// Returns the current owner of the child.
+    fn ensure_addable_child(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<AccountId, PSP34Error>;
+
+    /// Transfer the child to this contract and insert it under the parent token.
+    /// The child is given back to `sender` if it can't be inserted.
+    fn insert_child(
+        &mut self,
+        parent_token_id: Id,
+        child_nft: ChildNft,
+        accepted: bool,
+        sender: AccountId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Return the pending child to the account which sent it.
+    fn return_pending_child(
+        &mut self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Return the pending children of the parent token to the accounts which sent them.
+    fn return_pending_children(
+        &mut self,
+        parent_token_id: &Id,
+        max_rejections: u32,
+    ) -> Result<(), PSP34Error>;
+
+    /// Check that a batch call does not exceed `MAX_BATCH_CHILDREN` children
+    /// and names each child only once.
+    fn ensure_valid_batch(&self, children: &[ChildNft]) -> Result<(), PSP34Error>;
+
     /// Check if token is minted. Return the owner.
     fn ensure_exists(&self, id: &Id) -> Result<AccountId, PSP34Error>;
 
-    /// Check if caller is the owner of this parent token.
+    /// Get the direct owner of the token and the parent tokenId if the token is nested.
+    fn get_direct_owner(&self, token_id: &Id) -> Result<(AccountId, Option<Id>), PSP34Error>;
+
+    /// Get the owner of the top most parent token.
+    fn get_root_owner(&self, token_id: &Id) -> Result<AccountId, PSP34Error>;
+
+    /// Record the parent token in the child collection.
+    fn update_child_parent(
+        &mut self,
+        child_nft: &ChildNft,
+        parent_token_id: Option<Id>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Hand the child over to `to` and clear its parent record.
+    fn release_child(
+        &mut self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+        to: AccountId,
+    ) -> Result<(), PSP34Error>;
+
+    /// Transfer the child to `to`, within this collection or through a cross contract call.
+    fn move_child(&mut self, to: AccountId, child_nft: ChildNft) -> Result<(), PSP34Error>;
+
+    /// Check that the child is not an ancestor of the parent token
+    /// and that the maximum nesting depth is respected.
+    fn ensure_valid_nesting(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Get the number of levels of accepted and pending children below the token.
+    fn get_children_depth(&self, token: &ChildNft) -> Result<u32, PSP34Error>;
+
+    /// Check if the child collection is approved by this (parent) collection.
+    fn ensure_allowed_child_collection(&self, collection: &CollectionId) -> Result<(), PSP34Error>;
+
+    /// Check if caller is the root owner of this parent token or an operator approved by it.
+    /// Operators of a nested parent must be approved by the root owner for all tokens.
     fn is_caller_parent_owner(
         &self,
         caller: AccountId,
         parent_token_id: &Id,
     ) -> Result<(), PSP34Error>;
 
+    /// Hook called before an accepted child leaves the parent token.
+    /// Returns error if the child must stay with its parent.
+    fn _before_child_removed(
+        &self,
+        parent_token_id: &Id,
+        child_nft: &ChildNft,
+    ) -> Result<(), PSP34Error>;
+
+    /// Burn the token, its accepted children (recursively) and release the pending children.
+    /// Returns the number of burned children.
+    fn burn_recursive(
+        &mut self,
+        token_id: &Id,
+        max_recursive_burns: u32,
+    ) -> Result<u32, PSP34Error>;
+
     /// Cross contract call to transfer child nft ownership.
     fn transfer_child_ownership(
         &self,
         to: AccountId,
         child_nft: ChildNft,
     ) -> Result<(), PSP34Error>;
+
+    /// Cross contract call to record the parent token in the child collection.
+    fn set_child_parent(
+        &self,
+        child_nft: &ChildNft,
+        parent_token_id: Option<Id>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Cross contract calls to check that the parent token in another collection exists
+    /// and takes `amount` more pending children from this collection.
+    fn ensure_remote_parent(
+        &self,
+        collection: AccountId,
+        parent_token_id: &Id,
+        amount: u64,
+    ) -> Result<(), PSP34Error>;
+
+    /// Approve the parent collection for the token and let it add the token as a child.
+    /// The approval is revoked if the parent collection refuses the token.
+    fn nest_into_collection(
+        &mut self,
+        collection: AccountId,
+        parent_token_id: Id,
+        token_id: Id,
+    ) -> Result<(), PSP34Error>;
 }
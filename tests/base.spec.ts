@@ -16,6 +16,7 @@ const MAX_SUPPLY = 888;
 const BASE_URI = "ipfs://tokenUriPrefix/";
 const COLLECTION_METADATA = "ipfs://collectionMetadata/data.json";
 const BASE_METADATA = "ipfs://baseMetadata";
+const BASE_ID = 0;
 const ONE = new BN(10).pow(new BN(18));
 const PRICE_PER_MINT = ONE;
 
@@ -72,9 +73,10 @@ describe('RMRK Base tests', () => {
   it('Setup Base', async () => {
     await setup();
 
-    // set Base metadata
-    const setupBaseGas = (await gem.query.setupBase([BASE_METADATA])).gasRequired;
-    await gem.withSigner(deployer).tx.setupBase([BASE_METADATA], { gasLimit: setupBaseGas * 2n });
+    // register a new Base
+    const addBaseGas = (await gem.withSigner(deployer).query.addBase([BASE_METADATA])).gasRequired;
+    await gem.withSigner(deployer).tx.addBase([BASE_METADATA], { gasLimit: addBaseGas * 2n });
+    expect((await gem.query.getBases())?.value).to.have.lengthOf(1);
 
     // define 2 test Parts
     const PART_LIST: Part[] = [
@@ -95,20 +97,20 @@ describe('RMRK Base tests', () => {
     ];
 
     // add parts to base
-    const addPartListGas = (await gem.withSigner(deployer).query.addPartList(PART_LIST)).gasRequired;
-    await gem.withSigner(deployer).tx.addPartList(PART_LIST, { gasLimit: addPartListGas * 2n });
-    expect((await gem.query.getPartsCount())?.value).to.be.equal(2);
+    const addPartListGas = (await gem.withSigner(deployer).query.addPartList(BASE_ID, PART_LIST)).gasRequired;
+    await gem.withSigner(deployer).tx.addPartList(BASE_ID, PART_LIST, { gasLimit: addPartListGas * 2n });
+    expect((await gem.query.getPartsCount(BASE_ID))?.value).to.be.equal(2);
     
     // add/remove equippable addresses
-    const addEquipGas = (await gem.withSigner(deployer).query.addEquippableAddresses(0, [kanaria.address])).gasRequired;
-    await gem.withSigner(deployer).tx.addEquippableAddresses(0, [kanaria.address], { gasLimit: addEquipGas * 2n });
-    expect((await gem.query.isEquippable(0, kanaria.address))?.value).to.be.true;
-    expect((await gem.query.isEquippable(1, kanaria.address))?.value).to.be.false;
-    const removePartListGas = (await gem.withSigner(deployer).query.resetEquippableAddresses(0)).gasRequired;
-    await gem.withSigner(deployer).tx.resetEquippableAddresses(0, { gasLimit: removePartListGas * 2n });
+    const addEquipGas = (await gem.withSigner(deployer).query.addEquippableAddresses(BASE_ID, 0, [kanaria.address])).gasRequired;
+    await gem.withSigner(deployer).tx.addEquippableAddresses(BASE_ID, 0, [kanaria.address], { gasLimit: addEquipGas * 2n });
+    expect((await gem.query.isEquippable(BASE_ID, 0, kanaria.address))?.value).to.be.true;
+    expect((await gem.query.isEquippable(BASE_ID, 1, kanaria.address))?.value).to.be.false;
+    const removePartListGas = (await gem.withSigner(deployer).query.resetEquippableAddresses(BASE_ID, 0)).gasRequired;
+    await gem.withSigner(deployer).tx.resetEquippableAddresses(BASE_ID, 0, { gasLimit: removePartListGas * 2n });
 
     // should fail in attempt to add equippable address to fixed part.
-    const failAddEquip = (await gem.withSigner(deployer).query.addEquippableAddresses(1, [kanaria.address]));
+    const failAddEquip = (await gem.withSigner(deployer).query.addEquippableAddresses(BASE_ID, 1, [kanaria.address]));
     expect(hex2a(failAddEquip.value.err.custom)).to.be.equal('PartIsNotSlot');
 
   })
@@ -73,6 +73,10 @@ describe('RMRK Multi Asset tests', () => {
       dave.address,
       100,
     )).address, deployer, api);
+
+    // deployer approves gem collection to be nested into kanaria tokens
+    const allowGas = (await kanaria.query.addAllowedChildCollection(gem.address)).gasRequired;
+    await kanaria.tx.addAllowedChildCollection(gem.address, { gasLimit: allowGas });
   }
 
   it('Init two rmrk contracts works', async () => {
@@ -122,10 +126,10 @@ describe('RMRK Multi Asset tests', () => {
     expect((await gem.query.balanceOf(bob.address)).value).to.equal(15);
     
     // deployer adds two assets for kanaria
-    const assetEntryGas = (await kanaria.withSigner(deployer).query.addAssetEntry(assetDefaultId, "1", ["ipfs://default.png"])).gasRequired;
-    const addAssetResult = await kanaria.withSigner(deployer).tx.addAssetEntry(assetDefaultId, "1", ["ipfs://default.png"], { gasLimit: assetEntryGas * 2n });
+    const assetEntryGas = (await kanaria.withSigner(deployer).query.addAssetEntry(assetDefaultId, "1", null, null, ["ipfs://default.png"], [])).gasRequired;
+    const addAssetResult = await kanaria.withSigner(deployer).tx.addAssetEntry(assetDefaultId, "1", null, null, ["ipfs://default.png"], [], { gasLimit: assetEntryGas * 2n });
     emit(addAssetResult, 'AssetSet', { asset: 1 });
-    await kanaria.withSigner(deployer).tx.addAssetEntry(assetComposedId, "1", ["ipfs://meta1.json"], { gasLimit: assetEntryGas * 2n });
+    await kanaria.withSigner(deployer).tx.addAssetEntry(assetComposedId, "1", null, null, ["ipfs://meta1.json"], [], { gasLimit: assetEntryGas * 2n });
     expect((await kanaria.withSigner(deployer).query.totalAssets())?.value.toString()).to.be.equal("2");
     
     // add both assets to token 1
@@ -147,15 +151,15 @@ describe('RMRK Multi Asset tests', () => {
     const equippableRefIdLeftGem = 1;
     const equippableRefIdMidGem = 2;
     const equippableRefIdRightGem = 3;
-    const gemAssetAddGas = (await gem.withSigner(deployer).query.addAssetEntry(0, 0,  ["ipfs://gems/typeA/full.svg"])).gasRequired;
-    await gem.withSigner(deployer).tx.addAssetEntry(1, 0, ["ipfs://gems/typeA/full.svg"], { gasLimit: gemAssetAddGas });
-    await gem.withSigner(deployer).tx.addAssetEntry(2, equippableRefIdLeftGem,  ["ipfs://gems/typeA/left.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(3, equippableRefIdMidGem,  ["ipfs://gems/typeA/mid.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(4, equippableRefIdRightGem,  ["ipfs://gems/typeA/right.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(5, 0, ["ipfs://gems/typeB/full.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(6, equippableRefIdLeftGem,  ["ipfs://gems/typeB/left.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(7, equippableRefIdMidGem,  ["ipfs://gems/typeB/mid.svg"], { gasLimit: gemAssetAddGas * 2n });
-    await gem.withSigner(deployer).tx.addAssetEntry(8, equippableRefIdRightGem, ["ipfs://gems/typeB/right.svg"], { gasLimit: gemAssetAddGas * 2n });
+    const gemAssetAddGas = (await gem.withSigner(deployer).query.addAssetEntry(0, 0, null, null, ["ipfs://gems/typeA/full.svg"], [])).gasRequired;
+    await gem.withSigner(deployer).tx.addAssetEntry(1, 0, null, null, ["ipfs://gems/typeA/full.svg"], [], { gasLimit: gemAssetAddGas });
+    await gem.withSigner(deployer).tx.addAssetEntry(2, equippableRefIdLeftGem, null, null, ["ipfs://gems/typeA/left.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(3, equippableRefIdMidGem, null, null, ["ipfs://gems/typeA/mid.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(4, equippableRefIdRightGem, null, null, ["ipfs://gems/typeA/right.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(5, 0, null, null, ["ipfs://gems/typeB/full.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(6, equippableRefIdLeftGem, null, null, ["ipfs://gems/typeB/left.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(7, equippableRefIdMidGem, null, null, ["ipfs://gems/typeB/mid.svg"], [], { gasLimit: gemAssetAddGas * 2n });
+    await gem.withSigner(deployer).tx.addAssetEntry(8, equippableRefIdRightGem, null, null, ["ipfs://gems/typeB/right.svg"], [], { gasLimit: gemAssetAddGas * 2n });
     expect((await gem.withSigner(deployer).query.totalAssets())?.value.toString()).to.be.equal("8");
 
     // We add assets of type A to gem 1 and 2, and type Bto gem 3. Both are nested into the first kanaria
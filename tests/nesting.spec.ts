@@ -68,6 +68,10 @@ describe('RMRK Nesting tests', () => {
       ZERO_ADDRESS,
       0,
     )).address, deployer, api);
+
+    // deployer approves child collection to be nested into parent tokens
+    const allowGas = (await parent.query.addAllowedChildCollection(child.address)).gasRequired;
+    await parent.tx.addAllowedChildCollection(child.address, { gasLimit: allowGas });
   }
 
   it('Init two rmrk contracts works', async () => {
@@ -176,6 +180,95 @@ describe('RMRK Nesting tests', () => {
     expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,0");
   })
 
+  it('Reject all children returns them to senders', async () => {
+    await setup();
+
+    // bob mints parent, dave mints child
+    const mintGas = (await parent.withSigner(bob).query.mintNext()).gasRequired;
+    await parent.withSigner(bob).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+    await child.withSigner(dave).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+
+    // dave adds child nft to bob's parent nft
+    const approveGas = (await child.withSigner(dave).query.approve(parent.address, { u64: 1 }, true)).gasRequired;
+    await child.withSigner(dave).tx.approve(parent.address, { u64: 1 }, true, { gasLimit: approveGas });
+    const addChildGas = (await parent.withSigner(dave).query.addChild({ u64: 1 }, [child.address, { u64: 1 }])).gasRequired;
+    await parent.withSigner(dave).tx.addChild({ u64: 1 }, [child.address, { u64: 1 }], { gasLimit: addChildGas });
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,1");
+
+    // bob fails to reject more children than expected
+    const failRejectResult = await parent.withSigner(bob).query.rejectAllChildren({ u64: 1 }, 0);
+    expect(hex2a(failRejectResult.value.err.custom)).to.be.equal('MaxRejectionsReached');
+
+    // bob rejects all children, child is returned to dave
+    const rejectGas = (await parent.withSigner(bob).query.rejectAllChildren({ u64: 1 }, 1)).gasRequired;
+    const rejectResult = await parent.withSigner(bob).tx.rejectAllChildren({ u64: 1 }, 1, { gasLimit: rejectGas * 2n });
+    emit(rejectResult, 'ChildRejected', { parent: { u64: 1 }, childCollection: child.address, childTokenId: { u64: 1 } });
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,0");
+    expect((await child.query.ownerOf({ u64: 1 })).value).to.equal(dave.address);
+  })
+
+  it('Transfer of parent returns pending children to senders', async () => {
+    await setup();
+
+    // bob mints parent, dave mints child and adds it to bob's parent
+    const mintGas = (await parent.withSigner(bob).query.mintNext()).gasRequired;
+    await parent.withSigner(bob).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+    await child.withSigner(dave).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+    const approveGas = (await child.withSigner(dave).query.approve(parent.address, { u64: 1 }, true)).gasRequired;
+    await child.withSigner(dave).tx.approve(parent.address, { u64: 1 }, true, { gasLimit: approveGas });
+    const addChildGas = (await parent.withSigner(dave).query.addChild({ u64: 1 }, [child.address, { u64: 1 }])).gasRequired;
+    await parent.withSigner(dave).tx.addChild({ u64: 1 }, [child.address, { u64: 1 }], { gasLimit: addChildGas });
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,1");
+
+    // bob transfers parent to deployer, pending child doesn't carry over
+    const transferGas = (await parent.withSigner(bob).query.transfer(deployer.address, { u64: 1 }, [])).gasRequired;
+    await parent.withSigner(bob).tx.transfer(deployer.address, { u64: 1 }, [], { gasLimit: transferGas * 2n });
+    expect((await parent.query.ownerOf({ u64: 1 })).value).to.equal(deployer.address);
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,0");
+    expect((await child.query.ownerOf({ u64: 1 })).value).to.equal(dave.address);
+  })
+
+  it('Nest transfer into other collection works', async () => {
+    await setup();
+
+    // bob mints parent, dave mints child
+    const mintGas = (await parent.withSigner(bob).query.mintNext()).gasRequired;
+    await parent.withSigner(bob).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+    await child.withSigner(dave).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+
+    // dave nests his child into bob's parent without approving it first
+    const nestTransferGas = (await child.withSigner(dave).query.nestTransfer(parent.address, { u64: 1 }, { u64: 1 })).gasRequired;
+    await child.withSigner(dave).tx.nestTransfer(parent.address, { u64: 1 }, { u64: 1 }, { gasLimit: nestTransferGas * 2n });
+    expect((await child.query.ownerOf({ u64: 1 })).value).to.equal(parent.address);
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,1");
+
+    // nesting into a missing parent fails and leaves the token with dave
+    await child.withSigner(dave).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+    const failResult = await child.withSigner(dave).query.nestTransfer(parent.address, { u64: 42 }, { u64: 2 });
+    expect(failResult.value.err).to.not.be.undefined;
+    await child.withSigner(dave).tx.nestTransfer(parent.address, { u64: 42 }, { u64: 2 }, { gasLimit: nestTransferGas * 2n });
+    expect((await child.query.ownerOf({ u64: 2 })).value).to.equal(dave.address);
+    expect((await child.query.allowance(dave.address, parent.address, { u64: 2 })).value).to.equal(false);
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,1");
+  })
+
+  it('Add child from non PSP34 address fails', async () => {
+    await setup();
+
+    // bob mints parent
+    const mintGas = (await parent.withSigner(bob).query.mintNext()).gasRequired;
+    await parent.withSigner(bob).tx.mintNext({ value: PRICE_PER_MINT, gasLimit: mintGas * 2n });
+
+    // deployer accepts children from any address
+    const allowAllGas = (await parent.query.setAllowAllChildCollections(true)).gasRequired;
+    await parent.tx.setAllowAllChildCollections(true, { gasLimit: allowAllGas });
+
+    // dave's account is not a PSP34 contract
+    const result = await parent.withSigner(bob).query.addChild({ u64: 1 }, [dave.address, { u64: 1 }]);
+    expect(hex2a(result.value.err.custom)).to.be.equal('ChildContractNotPsp34');
+    expect((await parent.query.childrenBalance({ u64: 1 }))?.value.ok.toString()).to.be.equal("0,0");
+  })
+
   it('Add child (same user) works', async () => {
     await setup();
 